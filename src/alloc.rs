@@ -0,0 +1,178 @@
+// Учёт выделений памяти в куче: `start --alloc` печатает для каждого раздела,
+// сколько раз он выделял и освобождал память и сколько байт занимал в пике.
+// Так видно, например, что `Pair(Box<i32>, Box<i32>)` — это два выделения,
+// а `destroy` освобождает их оба.
+//
+// Считает только `Counting`, установленный как `#[global_allocator]` (это делает `main.rs`).
+// Счётчики общие для всей программы, поэтому разделы нужно запускать по одному.
+// Служебная работа самого `start` посреди раздела (например, записи хроники `Traced`)
+// выполняется в `uncounted` и в замер не попадает.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+// Системный аллокатор со счётчиками
+pub struct Counting;
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+static ALLOCS: AtomicUsize = AtomicUsize::new(0);
+static DEALLOCS: AtomicUsize = AtomicUsize::new(0);
+static REALLOCS: AtomicUsize = AtomicUsize::new(0);
+// Сколько байт занято сейчас и максимум с последнего `Scope::new`
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    // Внутри `uncounted`: выделения этого потока не считаются
+    static PAUSED: Cell<bool> = const { Cell::new(false) };
+}
+
+fn counted() -> bool {
+    !PAUSED.get()
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() && counted() {
+            ALLOCS.fetch_add(1, Ordering::Relaxed);
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() && counted() {
+            ALLOCS.fetch_add(1, Ordering::Relaxed);
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        if counted() {
+            DEALLOCS.fetch_add(1, Ordering::Relaxed);
+            shrink(layout.size());
+        }
+    }
+
+    // Например, рост `Vec` или `String` при добавлении элементов
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() && counted() {
+            REALLOCS.fetch_add(1, Ordering::Relaxed);
+            shrink(layout.size());
+            grow(new_size);
+        }
+        new_ptr
+    }
+}
+
+fn grow(size: usize) {
+    INSTALLED.store(true, Ordering::Relaxed);
+    let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+// Не уходит ниже нуля, даже если освобождается память, выделенная до установки `Counting`
+// или внутри `uncounted`
+fn shrink(size: usize) {
+    let _ = CURRENT.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+        Some(current.saturating_sub(size))
+    });
+}
+
+// Выполняет `f`, не считая выделений памяти в этом потоке.
+// Память, выделенная внутри `uncounted`, должна и освобождаться внутри `uncounted`,
+// иначе счётчик занятой памяти уменьшится на то, что в него не прибавлялось.
+pub fn uncounted<T>(f: impl FnOnce() -> T) -> T {
+    // Счёт возобновляется и при панике внутри `f`
+    struct Resume(bool);
+    impl Drop for Resume {
+        fn drop(&mut self) {
+            PAUSED.set(self.0);
+        }
+    }
+    let _resume = Resume(PAUSED.replace(true));
+    f()
+}
+
+// Установлен ли `Counting`: без него все счётчики остаются нулевыми
+pub fn is_installed() -> bool {
+    INSTALLED.load(Ordering::Relaxed)
+}
+
+// Память, выделенная за время замера
+#[derive(Clone, Copy, Default)]
+pub struct Stats {
+    pub allocs: usize,
+    pub deallocs: usize,
+    pub reallocs: usize,
+    // Наибольший прирост занятой памяти относительно начала замера, в байтах
+    pub peak: usize,
+}
+
+// Замер: при выходе из области видимости, в том числе из-за паники,
+// записывает итог в `stats`. Сообщение паники тоже выделяет память и попадает в замер.
+pub struct Scope<'a> {
+    stats: &'a Cell<Stats>,
+    allocs: usize,
+    deallocs: usize,
+    reallocs: usize,
+    current: usize,
+}
+
+impl<'a> Scope<'a> {
+    pub fn new(stats: &'a Cell<Stats>) -> Scope<'a> {
+        let current = CURRENT.load(Ordering::Relaxed);
+        PEAK.store(current, Ordering::Relaxed);
+        Scope {
+            stats,
+            allocs: ALLOCS.load(Ordering::Relaxed),
+            deallocs: DEALLOCS.load(Ordering::Relaxed),
+            reallocs: REALLOCS.load(Ordering::Relaxed),
+            current,
+        }
+    }
+}
+
+impl Drop for Scope<'_> {
+    fn drop(&mut self) {
+        self.stats.set(Stats {
+            allocs: ALLOCS.load(Ordering::Relaxed) - self.allocs,
+            deallocs: DEALLOCS.load(Ordering::Relaxed) - self.deallocs,
+            reallocs: REALLOCS.load(Ordering::Relaxed) - self.reallocs,
+            peak: PEAK.load(Ordering::Relaxed).saturating_sub(self.current),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Тестам библиотеки `main.rs` аллокатор не устанавливает
+    #[global_allocator]
+    static COUNTING: Counting = Counting;
+
+    // Тесты идут в нескольких потоках, а счётчики общие,
+    // поэтому чужие выделения могут только увеличить итог
+    #[test]
+    fn scope_counts_its_allocations() {
+        let stats = Cell::new(Stats::default());
+        {
+            let _scope = Scope::new(&stats);
+            let mut numbers: Vec<u64> = Vec::with_capacity(1024);
+            numbers.extend(0..1024);
+            std::hint::black_box(&numbers);
+        }
+        assert!(is_installed());
+        let stats = stats.get();
+        assert!(stats.allocs >= 1);
+        assert!(stats.deallocs >= 1);
+        assert!(stats.peak >= 1024 * 8, "пик {} байт", stats.peak);
+    }
+}
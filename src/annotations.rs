@@ -0,0 +1,253 @@
+// Ожидаемый вывод разделов, записанный прямо в исходниках: строки `// >> ...`
+// после `println!` или многострочные блоки `/* >> ... */`.
+// Аннотация `// >> ...` (многоточие) заменяет любое число строк, например середину fizzbuzz.
+
+use std::ops::Range;
+
+use crate::book::Section;
+
+// Одна строка ожидаемого вывода
+#[derive(Debug)]
+pub struct Annotation {
+    // Номер строки в исходном файле (с нуля)
+    pub line: usize,
+    pub text: String,
+    // Где в строке исходника записан текст: всё до и после сохраняется при перезаписи
+    pub columns: Range<usize>,
+}
+
+// Строки исходника, которые занимает функция раздела: от сигнатуры до закрывающей скобки
+pub fn fn_span(source: &str, name: &str) -> Option<Range<usize>> {
+    let lines: Vec<&str> = source.lines().collect();
+    let signature = format!("fn {}(", name);
+    let start = lines.iter().position(|line| {
+        let line = line.strip_prefix("pub ").unwrap_or(line);
+        line.starts_with(&signature)
+    })?;
+    let len = lines[start..].iter().position(|line| *line == "}")?;
+    Some(start..start + len + 1)
+}
+
+// Ожидаемый вывод раздела в порядке следования в коде
+pub fn expected(section: &Section) -> Vec<Annotation> {
+    match fn_span(section.source, section.func_name()) {
+        Some(span) => parse(&section.source.lines().collect::<Vec<_>>(), span),
+        None => Vec::new(),
+    }
+}
+
+fn parse(lines: &[&str], span: Range<usize>) -> Vec<Annotation> {
+    let mut annotations = Vec::new();
+    let mut i = span.start;
+
+    while i < span.end {
+        let line = lines[i].trim();
+        if let Some(text) = line.strip_prefix("// >>") {
+            let text = strip_marker_space(text);
+            annotations.push(Annotation {
+                line: i,
+                text: text.to_string(),
+                columns: columns(lines[i], text),
+            });
+        } else if line.starts_with("/*") {
+            let end = block_end(lines, i);
+            if is_annotation_block(&lines[i..=end]) {
+                for (j, content) in block_contents(&lines[i..=end]) {
+                    let text = match content.strip_prefix(">>") {
+                        Some(text) => strip_marker_space(text),
+                        None => content,
+                    };
+                    annotations.push(Annotation {
+                        line: i + j,
+                        text: text.to_string(),
+                        columns: columns(lines[i + j], text),
+                    });
+                }
+            }
+            // Обычные блочные комментарии пропускаем целиком: `// >>` внутри них — просто текст
+            i = end;
+        }
+        i += 1;
+    }
+    annotations
+}
+
+// Строка, на которой закрывается блочный комментарий, начатый на строке `start`.
+// Блочные комментарии в Rust могут быть вложенными.
+pub fn block_end(lines: &[&str], start: usize) -> usize {
+    let mut depth = 0;
+    for (i, line) in lines.iter().enumerate().skip(start) {
+        depth += line.matches("/*").count() as i32;
+        depth -= line.matches("*/").count() as i32;
+        if depth <= 0 {
+            return i;
+        }
+    }
+    lines.len() - 1
+}
+
+// Блок считается аннотацией, если его первая непустая строка начинается с `>>`
+fn is_annotation_block(block: &[&str]) -> bool {
+    block_contents(block)
+        .first()
+        .is_some_and(|(_, content)| content.starts_with(">>"))
+}
+
+// Непустые строки блока без `/*`, `*/` и отступа самого блока вместе с их смещением от начала блока.
+// Отступ сверх отступа блока — часть вывода, например у полей в `{:#?}`.
+fn block_contents<'a>(block: &[&'a str]) -> Vec<(usize, &'a str)> {
+    let last = block.len() - 1;
+    let indent = block[0].len() - block[0].trim_start().len();
+    block
+        .iter()
+        .enumerate()
+        .filter_map(|(j, line)| {
+            let mut content = line.trim_end();
+            let unindented = content.trim_start();
+            content = if j == 0 || unindented.starts_with(">>") {
+                unindented
+            } else {
+                &content[(content.len() - unindented.len()).min(indent)..]
+            };
+            if j == 0 {
+                content = content.trim_start_matches("/*").trim_start();
+            }
+            if j == last {
+                content = content.trim_end_matches("*/").trim_end();
+            }
+            (!content.trim_start().is_empty()).then_some((j, content))
+        })
+        .collect()
+}
+
+// Положение подстроки `part` внутри строки `line`
+fn columns(line: &str, part: &str) -> Range<usize> {
+    let start = part.as_ptr() as usize - line.as_ptr() as usize;
+    start..start + part.len()
+}
+
+// После маркера `>>` ставится один пробел; остальные пробелы — часть вывода
+fn strip_marker_space(text: &str) -> &str {
+    text.strip_prefix(' ').unwrap_or(text)
+}
+
+// Изменение при сравнении ожидаемых строк с фактическими
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Change {
+    // Строка совпала: индекс ожидаемой и фактической строк
+    Same(usize, usize),
+    // Ожидаемая строка не была выведена
+    Missing(usize),
+    // Выведена строка, которой нет среди ожидаемых
+    Unexpected(usize),
+}
+
+pub const ELLIPSIS: &str = "...";
+
+// Построчное сравнение через наибольшую общую подпоследовательность.
+// Строки сравниваются точно, вместе с пробелами: отступ — тоже часть вывода.
+pub fn diff(expected: &[&str], actual: &[&str]) -> Vec<Change> {
+    let (n, m) = (expected.len(), actual.len());
+    let is_ellipsis = |i: usize| expected[i].trim() == ELLIPSIS;
+    // lcs[i][j] — длина общей подпоследовательности `expected[i..]` и `actual[j..]`.
+    // Многоточие совпадает всегда: оно либо поглощает очередную строку, либо заканчивается.
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..=m).rev() {
+            lcs[i][j] = if is_ellipsis(i) {
+                let consumed = if j < m { lcs[i][j + 1] } else { 0 };
+                consumed.max(lcs[i + 1][j] + 1)
+            } else if j == m {
+                0
+            } else if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut changes = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && is_ellipsis(i) {
+            if j < m && lcs[i][j + 1] > lcs[i + 1][j] {
+                changes.push(Change::Same(i, j));
+                j += 1;
+            } else {
+                i += 1;
+            }
+        } else if i < n && j < m && expected[i] == actual[j] {
+            changes.push(Change::Same(i, j));
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            changes.push(Change::Missing(i));
+            i += 1;
+        } else {
+            changes.push(Change::Unexpected(j));
+            j += 1;
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Change::*;
+
+    fn matches(expected: &[&str], actual: &[&str]) -> bool {
+        diff(expected, actual)
+            .iter()
+            .all(|change| matches!(change, Same(..)))
+    }
+
+    #[test]
+    fn ellipsis_matches_any_number_of_lines() {
+        assert!(matches(&["1", "...", "5"], &["1", "2", "3", "4", "5"]));
+        assert!(matches(&["1", "...", "5"], &["1", "5"]));
+        assert!(matches(&["1", "..."], &["1", "2", "3"]));
+        assert!(!matches(&["1", "...", "5"], &["1", "2", "3"]));
+    }
+
+    #[test]
+    fn reports_missing_and_unexpected_lines() {
+        assert_eq!(
+            diff(&["a", "b", "c"], &["a", "x", "c"]),
+            [Same(0, 0), Missing(1), Unexpected(1), Same(2, 2)]
+        );
+        assert_eq!(diff(&["a"], &["a", "b"]), [Same(0, 0), Unexpected(1)]);
+    }
+
+    #[test]
+    fn whitespace_is_part_of_the_line() {
+        assert_eq!(
+            diff(&[" x равен 5"], &["x равен 5"]),
+            [Missing(0), Unexpected(0)]
+        );
+    }
+
+    #[test]
+    fn parses_line_and_block_annotations() {
+        let source = "\
+fn section() {
+    println!(\"a\");
+    // >> a
+    println!(\"{:#?}\", person);
+    /*
+    >> Person {
+        name: \"Peter\",
+    }
+    */
+    /* Обычный комментарий
+    // >> не аннотация */
+}";
+        let lines: Vec<&str> = source.lines().collect();
+        let annotations = parse(&lines, fn_span(source, "section").unwrap());
+        let texts: Vec<&str> = annotations.iter().map(|a| a.text.as_str()).collect();
+        assert_eq!(texts, ["a", "Person {", "    name: \"Peter\",", "}"]);
+        let lines_of: Vec<usize> = annotations.iter().map(|a| a.line).collect();
+        assert_eq!(lines_of, [2, 5, 6, 7]);
+    }
+}
@@ -0,0 +1,161 @@
+// `start bench`: многократный запуск разделов с прогревом и замером времени.
+// Вывод разделов отправляется в `io::sink()`, так что измеряется сам код, а не терминал.
+// Раздел замеряется целиком, как его запускает книга (например, 8.3 и 8.4 сравнивают
+// fizzbuzz на `while` и на `for`), а раздел с вариантами — по одной строке на вариант.
+
+use std::io;
+use std::time::{Duration, Instant};
+
+use crate::book::Section;
+use crate::i18n::{self, Lang, Message};
+use crate::output::Output;
+use crate::runner;
+use crate::tr;
+
+pub struct Options {
+    // Запуски перед замерами, результаты которых отбрасываются
+    pub warmup: usize,
+    pub runs: usize,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            warmup: 10,
+            runs: 100,
+        }
+    }
+}
+
+// Отдельно замеряемый вариант кода раздела
+struct Variant {
+    section: &'static str,
+    name: Message,
+    run: fn(&Output),
+}
+
+// Разделы, код которых стоит сравнить по частям: императивная и функциональная
+// сумма квадратов из `func::func_fvp` замеряются каждая сама по себе
+const VARIANTS: &[Variant] = &[
+    #[cfg(feature = "ch-functions")]
+    Variant {
+        section: "9.3",
+        name: i18n::messages::BENCH_IMPERATIVE,
+        run: |out| {
+            let sum = crate::func::sum_of_squared_odd_imperative(std::hint::black_box(1000));
+            writeln!(out, "{}", sum);
+        },
+    },
+    #[cfg(feature = "ch-functions")]
+    Variant {
+        section: "9.3",
+        name: i18n::messages::BENCH_FUNCTIONAL,
+        run: |out| {
+            let sum = crate::func::sum_of_squared_odd_functional(std::hint::black_box(1000));
+            writeln!(out, "{}", sum);
+        },
+    },
+];
+
+// Что именно замеряется: раздел целиком или его вариант
+struct Case {
+    name: String,
+    run: fn(&Output),
+}
+
+// Разделы, которые замеряются без явного выбора: циклы fizzbuzz на `while` и `for`
+// и функции высшего порядка, где сумма квадратов считается в обоих стилях
+const DEFAULT_SECTIONS: &[&str] = &["8.3", "8.4", "9.3"];
+
+// Без явного выбора замеряются разделы из `DEFAULT_SECTIONS`
+pub fn default_sections(sections: Vec<&'static Section>) -> Vec<&'static Section> {
+    sections
+        .into_iter()
+        .filter(|section| DEFAULT_SECTIONS.contains(&section.number))
+        .collect()
+}
+
+// Возвращает `false`, если какой-то замер завершился паникой
+pub fn bench(sections: &[&Section], options: &Options) -> bool {
+    if cfg!(debug_assertions) {
+        println!("{}", tr!(BENCH_DEBUG));
+    }
+    println!("{}", tr!(BENCH_SETUP, options.warmup, options.runs));
+    println!();
+
+    // Варианты раздела замеряются вместо него самого.
+    // Разделы-заголовки и намеренно паникующие разделы замерять нечего.
+    let mut cases: Vec<Case> = Vec::new();
+    for section in sections {
+        let variants: Vec<&Variant> = VARIANTS
+            .iter()
+            .filter(|variant| variant.section == section.number)
+            .collect();
+        if variants.is_empty() {
+            if let Some(run) = section.run.filter(|_| !section.should_panic) {
+                cases.push(Case {
+                    name: format!("{} {}", section.number, section.title()),
+                    run,
+                });
+            }
+        }
+        for variant in variants {
+            cases.push(Case {
+                name: format!(
+                    "{} {}: {}",
+                    section.number,
+                    section.title(),
+                    variant.name.text()
+                ),
+                run: variant.run,
+            });
+        }
+    }
+
+    let width = cases
+        .iter()
+        .map(|case| case.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut ok = true;
+    for case in cases {
+        let name = format!("{:<width$}", case.name);
+        let time = |duration| format!("{:>10}", runner::format_duration(duration));
+        match measure(case.run, options) {
+            Ok(mut times) => {
+                times.sort();
+                let (min, median, max) = (times[0], times[times.len() / 2], times[times.len() - 1]);
+                println!(
+                    "{}",
+                    tr!(BENCH_RESULT, name, time(min), time(median), time(max))
+                );
+            }
+            Err(message) => {
+                ok = false;
+                println!("{}", tr!(BENCH_PANIC, name, message));
+            }
+        }
+    }
+    ok
+}
+
+// Время каждого из `options.runs` запусков после прогрева.
+// Вывод замеряется без перевода: перевод не относится к коду раздела.
+fn measure(run: fn(&Output), options: &Options) -> Result<Vec<Duration>, String> {
+    let mut sink = io::sink();
+    let out = Output::new(&mut sink);
+    i18n::with_lang(Lang::Ru, || {
+        runner::isolated(|| {
+            for _ in 0..options.warmup {
+                run(&out);
+            }
+            (0..options.runs.max(1))
+                .map(|_| {
+                    let start = Instant::now();
+                    run(&out);
+                    start.elapsed()
+                })
+                .collect()
+        })
+    })
+}
@@ -0,0 +1,309 @@
+// `start --bless`: перезапись аннотаций `// >>` и блоков `/* >> ... */` по фактическому выводу разделов
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+use crate::annotations::{self, Annotation, Change};
+use crate::book::Section;
+use crate::i18n::{self, Lang};
+use crate::runner;
+use crate::tr;
+
+// Правки одной строки исходника
+#[derive(Default)]
+struct LineEdit {
+    before: Vec<String>,
+    // `Some(None)` — удалить строку
+    replace: Option<Option<String>>,
+    after: Vec<String>,
+}
+
+// Возвращает `false`, если хотя бы один файл или раздел не удалось обработать
+pub fn bless(sections: &[&Section]) -> bool {
+    // Все правки одного файла применяются за один проход
+    let mut files: BTreeMap<&str, Vec<&Section>> = BTreeMap::new();
+    for section in sections.iter().filter(|section| section.run.is_some()) {
+        files.entry(section.file).or_default().push(section);
+    }
+
+    let (mut updated, mut ok) = (0, true);
+    for (file, sections) in files {
+        match bless_file(file, &sections) {
+            Ok((count, file_ok)) => {
+                updated += count;
+                ok &= file_ok;
+            }
+            Err(err) => {
+                println!("{}", tr!(STATUS_ERROR, format!("{}: {}", file, err)));
+                ok = false;
+            }
+        }
+    }
+
+    println!();
+    println!("{}", tr!(BLESS_TOTAL, updated));
+    if updated > 0 {
+        println!("{}", tr!(BLESS_REBUILD));
+    }
+    ok
+}
+
+fn bless_file(file: &str, sections: &[&Section]) -> Result<(usize, bool), String> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(file);
+    let on_disk = fs::read_to_string(&path).map_err(|err| err.to_string())?;
+    // Номера строк аннотаций берутся из исходника, встроенного при сборке
+    if on_disk != sections[0].source {
+        return Err(tr!(BLESS_CHANGED).to_string());
+    }
+
+    let lines: Vec<&str> = on_disk.lines().collect();
+    let mut edits = HashMap::new();
+    let (mut updated, mut ok) = (0, true);
+
+    for section in sections {
+        let title = format!(
+            "{} {} ({})",
+            section.number,
+            section.title(),
+            section.func()
+        );
+        let expected = annotations::expected(section);
+        if expected.is_empty() {
+            println!("{}", tr!(BLESS_SKIPPED, title));
+            continue;
+        }
+        // Аннотации остаются русскими при любом языке вывода
+        let output = match i18n::with_lang(Lang::Ru, || runner::capture(section)) {
+            Ok(output) => output,
+            Err(err) => {
+                println!("{}", tr!(STATUS_ERROR, title));
+                println!("    {}", err.replace('\n', "\n    "));
+                ok = false;
+                continue;
+            }
+        };
+
+        let actual: Vec<&str> = output.lines().map(str::trim_end).collect();
+        if plan(&lines, &expected, &actual, &mut edits) {
+            println!("{}", tr!(STATUS_UPDATED, title));
+            updated += 1;
+        } else {
+            println!("{}", tr!(STATUS_OK, title));
+        }
+    }
+
+    if updated > 0 {
+        let blessed = apply(&lines, edits, on_disk.ends_with('\n'));
+        fs::write(&path, blessed).map_err(|err| err.to_string())?;
+    }
+    Ok((updated, ok))
+}
+
+// Раскладывает расхождения по правкам строк исходника.
+// Возвращает `false`, если аннотации уже совпадают с выводом.
+fn plan(
+    lines: &[&str],
+    expected: &[Annotation],
+    actual: &[&str],
+    edits: &mut HashMap<usize, LineEdit>,
+) -> bool {
+    let expected_lines: Vec<&str> = expected.iter().map(|a| a.text.as_str()).collect();
+    let changes = annotations::diff(&expected_lines, actual);
+    if changes
+        .iter()
+        .all(|change| matches!(change, Change::Same(..)))
+    {
+        return false;
+    }
+
+    // Последняя обработанная аннотация: новые строки вывода вставляются после неё
+    let mut anchor = None;
+    let mut k = 0;
+    while k < changes.len() {
+        if let Change::Same(i, _) = changes[k] {
+            anchor = Some(i);
+            k += 1;
+            continue;
+        }
+
+        // Идущие подряд расхождения: пропавшие строки заменяем новыми по порядку,
+        // лишние удаляем, а оставшиеся новые вставляем следом
+        let (mut missing, mut unexpected) = (Vec::new(), Vec::new());
+        while let Some(change) = changes.get(k) {
+            match *change {
+                Change::Missing(i) => missing.push(i),
+                Change::Unexpected(j) => unexpected.push(actual[j]),
+                Change::Same(..) => break,
+            }
+            k += 1;
+        }
+
+        let mut new_lines = unexpected.into_iter();
+        for i in missing {
+            let annotation = &expected[i];
+            let line = lines[annotation.line];
+            let replacement = new_lines.next().map(|text| {
+                let mut prefix = line[..annotation.columns.start].to_string();
+                // Пустая аннотация могла быть записана как `// >>` без пробела
+                if prefix.ends_with(">>") {
+                    prefix.push(' ');
+                }
+                let blessed = format!("{}{}{}", prefix, text, &line[annotation.columns.end..]);
+                blessed.trim_end().to_string()
+            });
+            edits.entry(annotation.line).or_default().replace = Some(replacement);
+            anchor = Some(i);
+        }
+
+        let rest: Vec<&str> = new_lines.collect();
+        if rest.is_empty() {
+            continue;
+        }
+        match anchor {
+            Some(i) => {
+                let annotation = &expected[i];
+                let line = lines[annotation.line];
+                let inserted = rest.iter().map(|text| new_line(line, annotation, text));
+                edits
+                    .entry(annotation.line)
+                    .or_default()
+                    .after
+                    .extend(inserted);
+            }
+            // Расхождение в самом начале: вставляем перед первой совпавшей аннотацией
+            None => {
+                let Some(&Change::Same(i, _)) = changes.get(k) else {
+                    continue;
+                };
+                let annotation = &expected[i];
+                let line = lines[annotation.line];
+                let inserted = rest.iter().map(|text| new_line(line, annotation, text));
+                edits
+                    .entry(annotation.line)
+                    .or_default()
+                    .before
+                    .extend(inserted);
+            }
+        }
+    }
+    true
+}
+
+// Новая строка аннотации, оформленная так же, как соседняя
+fn new_line(line: &str, annotation: &Annotation, text: &str) -> String {
+    let prefix = &line[..annotation.columns.start];
+    let closes_block = line[annotation.columns.end..].contains("*/");
+    let blessed = if prefix.contains("/*") || closes_block {
+        let indent = &line[..line.len() - line.trim_start().len()];
+        format!("{}// >> {}", indent, text)
+    } else {
+        format!("{}{}", prefix, text)
+    };
+    blessed.trim_end().to_string()
+}
+
+fn apply(lines: &[&str], mut edits: HashMap<usize, LineEdit>, trailing_newline: bool) -> String {
+    let mut blessed = Vec::with_capacity(lines.len());
+    for (i, line) in lines.iter().enumerate() {
+        let edit = edits.remove(&i).unwrap_or_default();
+        blessed.extend(edit.before);
+        match edit.replace {
+            None => blessed.push(line.to_string()),
+            Some(Some(replacement)) => blessed.push(replacement),
+            Some(None) => {}
+        }
+        blessed.extend(edit.after);
+    }
+
+    let mut text = blessed.join("\n");
+    if trailing_newline {
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Исходник с функцией `section` после перезаписи её аннотаций по выводу `actual`;
+    // `None`, если аннотации уже совпадают с выводом
+    fn blessed(source: &'static str, actual: &[&str]) -> Option<String> {
+        let section = Section {
+            number: "1",
+            title: "",
+            path: "start::tests::section",
+            file: "src/tests.rs",
+            source,
+            run: None,
+            should_panic: false,
+        };
+        let lines: Vec<&str> = source.lines().collect();
+        let mut edits = HashMap::new();
+        let changed = plan(&lines, &annotations::expected(&section), actual, &mut edits);
+        changed.then(|| apply(&lines, edits, source.ends_with('\n')))
+    }
+
+    #[test]
+    fn leaves_matching_annotations_alone() {
+        let source = "fn section() {\n    // >> 4\n}\n";
+        assert_eq!(blessed(source, &["4"]), None);
+    }
+
+    #[test]
+    fn rewrites_line_annotations() {
+        let source = "\
+fn section() {
+    println!(\"{}\", a);
+    // >> 1
+    println!(\"{}\", b);
+    // >> 2
+    println!(\"{}\", c);
+    // >> 3
+}
+";
+        assert_eq!(
+            blessed(source, &["1", "20", "3", "4"]).unwrap(),
+            source
+                .replace("// >> 2", "// >> 20")
+                .replace("    // >> 3\n", "    // >> 3\n    // >> 4\n")
+        );
+        assert_eq!(
+            blessed(source, &["1", "3"]).unwrap(),
+            source.replace("    // >> 2\n", "")
+        );
+    }
+
+    #[test]
+    fn rewrites_block_annotations_keeping_indentation() {
+        let source = "\
+fn section() {
+    println!(\"{:#?}\", person);
+    /*
+    >> Person {
+        name: \"Peter\",
+    }
+    */
+}
+";
+        let actual = ["Person {", "    name: \"Paul\",", "    age: 27,", "}"];
+        assert_eq!(
+            blessed(source, &actual).unwrap(),
+            source.replace(
+                "        name: \"Peter\",\n",
+                "        name: \"Paul\",\n        age: 27,\n"
+            )
+        );
+    }
+
+    // Новая строка после однострочного блока записывается обычной аннотацией
+    #[test]
+    fn appends_line_annotations_after_a_one_line_block() {
+        let source = "fn section() {\n    /* >> 1 */\n}\n";
+        assert_eq!(
+            blessed(source, &["1", "2"]).unwrap(),
+            "fn section() {\n    /* >> 1 */\n    // >> 2\n}\n"
+        );
+    }
+}
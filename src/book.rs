@@ -0,0 +1,335 @@
+//! Оглавление книги: каждый модуль регистрирует свои разделы с номером и заголовком,
+//! а запуск, оглавление и прочие режимы работают поверх этого реестра.
+
+use std::fmt;
+
+use crate::output::Output;
+
+/// Раздел книги. Если `run` равен `None`, раздел — это только заголовок (например, `5 Типы`).
+pub struct Section {
+    /// Номер раздела, например `8.5.1.2`.
+    pub number: &'static str,
+    /// Русский заголовок раздела.
+    pub title: &'static str,
+    /// Путь к функции, которая реализует раздел, например `start::loop_control::match_enum`.
+    pub path: &'static str,
+    /// Файл модуля относительно корня крейта.
+    pub file: &'static str,
+    /// Исходный текст этого файла.
+    pub source: &'static str,
+    /// Функция раздела; `None` у разделов-заголовков.
+    pub run: Option<fn(&Output)>,
+    /// Раздел намеренно завершается паникой, например вызывает `foo() -> !`.
+    pub should_panic: bool,
+}
+
+// Регистрирует раздел, который реализован функцией `$func` текущего модуля.
+// `should_panic` в конце отмечает раздел, который намеренно завершается паникой.
+// Если все главы отключены возможностями Cargo, макросы никто не вызывает.
+#[allow(unused_macros)]
+macro_rules! section {
+    ($number:literal, $title:literal, $func:ident) => {
+        $crate::book::Section {
+            number: $number,
+            title: $title,
+            path: concat!(module_path!(), "::", stringify!($func)),
+            file: file!(),
+            source: include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", file!())),
+            run: Some($func),
+            should_panic: false,
+        }
+    };
+    ($number:literal, $title:literal, $func:ident, should_panic) => {
+        $crate::book::Section {
+            should_panic: true,
+            ..section!($number, $title, $func)
+        }
+    };
+}
+
+// Регистрирует раздел-заголовок без собственного кода
+#[allow(unused_macros)]
+macro_rules! heading {
+    ($number:literal, $title:literal) => {
+        $crate::book::Section {
+            number: $number,
+            title: $title,
+            path: "",
+            file: file!(),
+            source: include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", file!())),
+            run: None,
+            should_panic: false,
+        }
+    };
+}
+
+impl Section {
+    /// Разобранный номер раздела.
+    pub fn number(&self) -> SectionNumber {
+        SectionNumber::parse(self.number).expect("номер раздела в реестре должен быть корректным")
+    }
+
+    /// Путь к функции без имени крейта: `loop_control::match_enum`.
+    pub fn func(&self) -> &'static str {
+        match self.path.split_once("::") {
+            Some((_crate, path)) => path,
+            None => self.path,
+        }
+    }
+
+    /// Имя функции раздела: `match_enum`.
+    pub fn func_name(&self) -> &'static str {
+        self.path.rsplit("::").next().unwrap_or_default()
+    }
+
+    /// Заголовок на выбранном языке; поле `title` — русский заголовок из реестра.
+    pub fn title(&self) -> &'static str {
+        crate::i18n::title(self)
+    }
+
+    /// Теоретическая часть: комментарии между заголовком раздела и его функцией,
+    /// а в английском режиме — их перевод.
+    pub fn theory(&self) -> String {
+        crate::i18n::theory(self)
+    }
+
+    /// Глубина вложенности: `1` — глава, `1.2.2.1` — четвёртый уровень.
+    pub fn depth(&self) -> usize {
+        self.number.split('.').count()
+    }
+}
+
+// Глава книги и возможность Cargo `ch-*`, которая её включает
+struct Chapter {
+    feature: &'static str,
+    sections: &'static [Section],
+}
+
+// Список глав, включённых при сборке. Имя возможности записано один раз:
+// и в `cfg`, и в `Chapter::feature`, по которому `start watch` пересобирает с теми же главами.
+macro_rules! chapters {
+    ($($feature:literal => $module:ident,)*) => {
+        const CHAPTERS: &[Chapter] = &[$(
+            #[cfg(feature = $feature)]
+            Chapter {
+                feature: $feature,
+                sections: crate::$module::SECTIONS,
+            },
+        )*];
+    };
+}
+
+chapters! {
+    "ch-formatting" => getting_started,
+    "ch-primitives" => primitives,
+    "ch-custom-types" => use_type,
+    "ch-variable-bindings" => variable_binding,
+    "ch-types" => types,
+    "ch-conversion" => conversion,
+    "ch-expressions" => expressions,
+    "ch-flow-control" => loop_control,
+    "ch-functions" => func,
+    "ch-modules" => mods,
+}
+
+/// Все разделы книги в порядке чтения. Главы, отключённые возможностями `ch-*`, пропускаются.
+pub fn sections() -> Vec<&'static Section> {
+    CHAPTERS
+        .iter()
+        .flat_map(|chapter| chapter.sections.iter())
+        .collect()
+}
+
+/// Возможности `ch-*` глав, с которыми собрана программа.
+pub fn features() -> Vec<&'static str> {
+    CHAPTERS.iter().map(|chapter| chapter.feature).collect()
+}
+
+/// Номер раздела вида `8.5.1.2`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SectionNumber(Vec<u32>);
+
+impl SectionNumber {
+    /// Разбирает номер вида `8.5.1.2`; точка в конце допускается.
+    pub fn parse(s: &str) -> Result<SectionNumber, String> {
+        let parts = s
+            .trim_end_matches('.')
+            .split('.')
+            .map(|part| part.parse::<u32>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| crate::tr!(BAD_SECTION_NUMBER, s))?;
+        Ok(SectionNumber(parts))
+    }
+
+    /// `8.5.1.2` лежит внутри `8.5`, а также внутри самого себя.
+    pub fn starts_with(&self, prefix: &SectionNumber) -> bool {
+        self.0.starts_with(&prefix.0)
+    }
+
+    // Номер, обрезанный до `len` уровней: `8.5.1.2` -> `8.5`
+    fn truncated(&self, len: usize) -> SectionNumber {
+        SectionNumber(self.0.iter().copied().take(len).collect())
+    }
+}
+
+impl fmt::Display for SectionNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, part) in self.0.iter().enumerate() {
+            if i != 0 {
+                write!(f, ".")?;
+            }
+            write!(f, "{}", part)?;
+        }
+        Ok(())
+    }
+}
+
+/// Выбор разделов из командной строки: `start 8.5 9.2.1` или `start --from 3 --to 5`.
+#[derive(Debug, Default)]
+pub struct Selection {
+    /// Разделы, выбранные номерами, вместе с их подразделами.
+    pub numbers: Vec<SectionNumber>,
+    /// Начало диапазона `--from`.
+    pub from: Option<SectionNumber>,
+    /// Конец диапазона `--to`, включая его подразделы.
+    pub to: Option<SectionNumber>,
+}
+
+impl Selection {
+    /// Ничего не выбрано ни номерами, ни диапазоном.
+    pub fn is_empty(&self) -> bool {
+        self.numbers.is_empty() && self.from.is_none() && self.to.is_none()
+    }
+
+    /// Подходит ли раздел под выбор. Пустой выбор означает всю книгу.
+    pub fn matches(&self, number: &SectionNumber) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+        let in_range = (self.from.is_some() || self.to.is_some())
+            && self.from.as_ref().is_none_or(|from| number >= from)
+            // `--to 5` включает и все подразделы пятой главы
+            && self
+                .to
+                .as_ref()
+                .is_none_or(|to| number.truncated(to.0.len()) <= *to);
+        in_range || self.numbers.iter().any(|prefix| number.starts_with(prefix))
+    }
+
+    /// Разделы из `sections`, которые подходят под выбор.
+    pub fn select(&self, sections: Vec<&'static Section>) -> Vec<&'static Section> {
+        sections
+            .into_iter()
+            .filter(|section| self.matches(&section.number()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn number(s: &str) -> SectionNumber {
+        SectionNumber::parse(s).unwrap()
+    }
+
+    fn selection(numbers: &[&str], from: Option<&str>, to: Option<&str>) -> Selection {
+        Selection {
+            numbers: numbers.iter().map(|s| number(s)).collect(),
+            from: from.map(number),
+            to: to.map(number),
+        }
+    }
+
+    #[test]
+    fn parses_section_numbers() {
+        assert_eq!(number("8.5.1.2").to_string(), "8.5.1.2");
+        assert_eq!(number("8.5."), number("8.5"));
+        assert!(SectionNumber::parse("8.x").is_err());
+        assert!(number("8.10") > number("8.9"));
+    }
+
+    #[test]
+    fn empty_selection_matches_everything() {
+        assert!(Selection::default().matches(&number("9.2.1")));
+    }
+
+    #[test]
+    fn number_matches_its_subsections() {
+        let selection = selection(&["8.5", "9.2.1"], None, None);
+        assert!(selection.matches(&number("8.5")));
+        assert!(selection.matches(&number("8.5.1.2")));
+        assert!(selection.matches(&number("9.2.1")));
+        assert!(!selection.matches(&number("8.6")));
+        assert!(!selection.matches(&number("9.2")));
+        // `8.5` не означает `8.50`
+        assert!(!selection.matches(&number("8.50")));
+    }
+
+    #[test]
+    fn range_includes_subsections_of_its_end() {
+        let range = selection(&[], Some("3"), Some("5"));
+        assert!(!range.matches(&number("2.3")));
+        assert!(range.matches(&number("3")));
+        assert!(range.matches(&number("5.4")));
+        assert!(!range.matches(&number("6")));
+
+        let from = selection(&[], Some("8.5"), None);
+        assert!(from.matches(&number("9.4.1")));
+        assert!(!from.matches(&number("8.4")));
+    }
+
+    #[test]
+    fn numbers_and_range_combine() {
+        let selection = selection(&["1.2"], Some("8"), Some("8.2"));
+        assert!(selection.matches(&number("1.2.2.1")));
+        assert!(selection.matches(&number("8.2.1")));
+        assert!(!selection.matches(&number("8.3")));
+    }
+
+    // В реестре ровно те главы, чьи возможности `ch-*` включены при сборке
+    #[test]
+    fn chapters_follow_features() {
+        let enabled = [
+            ("1", cfg!(feature = "ch-formatting")),
+            ("2", cfg!(feature = "ch-primitives")),
+            ("3", cfg!(feature = "ch-custom-types")),
+            ("4", cfg!(feature = "ch-variable-bindings")),
+            ("5", cfg!(feature = "ch-types")),
+            ("6", cfg!(feature = "ch-conversion")),
+            ("7", cfg!(feature = "ch-expressions")),
+            ("8", cfg!(feature = "ch-flow-control")),
+            ("9", cfg!(feature = "ch-functions")),
+            ("10", cfg!(feature = "ch-modules")),
+        ];
+        let expected: Vec<&str> = enabled
+            .iter()
+            .filter(|(_, on)| *on)
+            .map(|(chapter, _)| *chapter)
+            .collect();
+        let chapters: Vec<&str> = sections()
+            .into_iter()
+            .filter(|section| section.depth() == 1)
+            .map(|section| section.number)
+            .collect();
+        assert_eq!(chapters, expected);
+    }
+
+    // Имена в `chapters!` — настоящие возможности из Cargo.toml, по одной на главу
+    #[test]
+    fn features_are_declared_in_the_manifest() {
+        let manifest = include_str!("../Cargo.toml");
+        let chapters = sections()
+            .into_iter()
+            .filter(|section| section.depth() == 1)
+            .count();
+        assert_eq!(features().len(), chapters);
+        for feature in features() {
+            assert!(
+                manifest.contains(&format!("\n{} = []", feature)),
+                "{}",
+                feature
+            );
+        }
+    }
+}
@@ -0,0 +1,121 @@
+// `start check <номер>`: проверка решений упражнений из `src/exercises`
+
+use crate::annotations::{self, Change};
+use crate::book::{SectionNumber, Selection};
+use crate::exercises::{self, Exercise};
+use crate::progress::Progress;
+use crate::runner;
+use crate::tr;
+
+// Упражнения к выбранным разделам
+pub fn select(selection: &Selection) -> Vec<&'static Exercise> {
+    exercises::EXERCISES
+        .iter()
+        .filter(|exercise| selection.matches(&number(exercise)))
+        .collect()
+}
+
+// Возвращает `true`, если все выбранные упражнения решены
+// Результаты записываются в `progress`.
+pub fn check(exercises: &[&Exercise], progress: &mut Progress) -> bool {
+    let mut solved = 0;
+    for exercise in exercises {
+        let passed = check_exercise(exercise);
+        progress.record_exercise(exercise.section, passed);
+        if passed {
+            solved += 1;
+        }
+    }
+
+    println!();
+    println!("{}", tr!(CHECK_TOTAL, solved, exercises.len()));
+    solved == exercises.len()
+}
+
+pub fn check_exercise(exercise: &Exercise) -> bool {
+    let title = format!(
+        "{} {} ({})",
+        exercise.section,
+        exercise.title.text(),
+        exercise.file
+    );
+
+    let output = match runner::capture_fn(exercise.check) {
+        Ok(output) => output,
+        Err(err) => {
+            println!("{}", tr!(STATUS_ERROR, title));
+            println!("    {}", err.replace('\n', "\n    "));
+            return false;
+        }
+    };
+
+    let expected: Vec<&str> = exercise.expected.lines().collect();
+    let actual: Vec<&str> = output.lines().collect();
+    let changes = annotations::diff(&expected, &actual);
+    if changes
+        .iter()
+        .all(|change| matches!(change, Change::Same(..)))
+    {
+        println!("{}", tr!(STATUS_SOLVED, title));
+        return true;
+    }
+
+    println!("{}", tr!(STATUS_WRONG, title));
+    for change in changes {
+        match change {
+            Change::Same(i, _) => println!("      {}", expected[i]),
+            Change::Missing(i) => println!("    - {}", expected[i]),
+            Change::Unexpected(j) => println!("    + {}", actual[j]),
+        }
+    }
+    false
+}
+
+pub fn number(exercise: &Exercise) -> SectionNumber {
+    SectionNumber::parse(exercise.section).expect("номер раздела упражнения должен быть корректным")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::Message;
+    use crate::output::Output;
+
+    fn exercise(check: fn(&Output), expected: &'static str) -> Exercise {
+        Exercise {
+            section: "1.2.2",
+            title: Message {
+                id: "TEST",
+                ru: "Проверка",
+                en: "Check",
+            },
+            file: "src/exercises/test.rs",
+            check,
+            expected,
+        }
+    }
+
+    fn two_lines(out: &Output) {
+        writeln!(out, "1 + 2i");
+        writeln!(out, "0 - 1i");
+    }
+
+    #[test]
+    fn solved_when_output_matches() {
+        assert!(check_exercise(&exercise(two_lines, "1 + 2i\n0 - 1i")));
+    }
+
+    #[test]
+    fn not_solved_when_output_differs() {
+        assert!(!check_exercise(&exercise(two_lines, "1 + 2i\n0 + -1i")));
+        assert!(!check_exercise(&exercise(two_lines, "1 + 2i")));
+    }
+
+    #[test]
+    fn panic_is_not_a_solution() {
+        fn panicking(_out: &Output) {
+            panic!("не реализовано");
+        }
+        assert!(!check_exercise(&exercise(panicking, "")));
+    }
+}
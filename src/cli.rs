@@ -0,0 +1,349 @@
+// Разбор аргументов командной строки
+
+use std::path::PathBuf;
+
+use start::bench;
+use start::book::{SectionNumber, Selection};
+use start::edit;
+use start::explain;
+use start::export::Format;
+use start::i18n::Lang;
+use start::quiz;
+use start::runner;
+use start::tr;
+
+pub enum Command {
+    Run(Selection, runner::Options),
+    Toc(Selection),
+    Progress,
+    Interactive(Selection),
+    Tui(Selection),
+    Edit(SectionNumber, edit::Options),
+    Quiz(Selection, quiz::Options),
+    Bench(Selection, bench::Options),
+    Search(String),
+    Export(Selection, Format, PathBuf),
+    Explain(Selection, explain::Options),
+    Serve(Selection, u16),
+    Verify(Selection),
+    Watch(Selection, Vec<String>),
+    Check(Selection),
+    Errors(Selection, bool),
+    Bless(Selection),
+    LangCheck,
+    Help,
+}
+
+// Язык из `--lang ru|en` в любом месте командной строки и остальные аргументы без него.
+// Язык выбирается до разбора команды, чтобы и ошибки разбора были на нужном языке.
+pub fn lang(args: Vec<String>) -> Result<(Option<Lang>, Vec<String>), String> {
+    let mut lang = None;
+    let mut rest = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--lang" {
+            let name = args.next().ok_or(tr!(LANG_EXPECTED))?;
+            lang = Some(Lang::parse(&name)?);
+        } else {
+            rest.push(arg);
+        }
+    }
+    Ok((lang, rest))
+}
+
+pub fn parse<I>(args: I) -> Result<Command, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter().peekable();
+
+    match args.peek().map(String::as_str) {
+        Some("list" | "toc") => {
+            args.next();
+            return Ok(Command::Toc(selection(args)?));
+        }
+        Some("progress") => return Ok(Command::Progress),
+        Some("search") => {
+            args.next();
+            let query = args.collect::<Vec<_>>().join(" ");
+            if query.trim().is_empty() {
+                return Err(tr!(CLI_QUERY_EXPECTED).to_string());
+            }
+            return Ok(Command::Search(query));
+        }
+        Some("explain") => {
+            args.next();
+            let mut options = explain::Options::default();
+            let mut rest = Vec::new();
+            for arg in args {
+                match arg.as_str() {
+                    "--source" => options.source = true,
+                    "--output" => options.output = true,
+                    _ => rest.push(arg),
+                }
+            }
+            let selection = selection(rest.into_iter())?;
+            if selection.is_empty() {
+                return Err(tr!(CLI_SECTION_EXPECTED, "explain"));
+            }
+            return Ok(Command::Explain(selection, options));
+        }
+        Some("export") => {
+            args.next();
+            let (mut format, mut dir) = (Format::Markdown, None);
+            let mut rest = Vec::new();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--format" => {
+                        let name = args.next().ok_or(tr!(CLI_EXPORT_FORMAT))?;
+                        format = Format::parse(&name)?;
+                    }
+                    "--out" => dir = Some(args.next().ok_or(tr!(CLI_EXPORT_OUT))?),
+                    _ => rest.push(arg),
+                }
+            }
+            let dir = dir.ok_or(tr!(CLI_EXPORT_DIR))?;
+            return Ok(Command::Export(
+                selection(rest.into_iter())?,
+                format,
+                PathBuf::from(dir),
+            ));
+        }
+        Some("serve") => {
+            args.next();
+            let mut port = 8080;
+            let mut rest = Vec::new();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--port" => port = number_value(&arg, args.next())?,
+                    _ => rest.push(arg),
+                }
+            }
+            return Ok(Command::Serve(selection(rest.into_iter())?, port));
+        }
+        Some("quiz") => {
+            args.next();
+            let mut options = quiz::Options::default();
+            let mut rest = Vec::new();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--choices" => options.choices = true,
+                    "--count" => options.count = number_value(&arg, args.next())?,
+                    "--seed" => options.seed = Some(number_value(&arg, args.next())?),
+                    _ => rest.push(arg),
+                }
+            }
+            return Ok(Command::Quiz(selection(rest.into_iter())?, options));
+        }
+        Some("bench") => {
+            args.next();
+            let mut options = bench::Options::default();
+            let mut rest = Vec::new();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--warmup" => options.warmup = number_value(&arg, args.next())?,
+                    "--runs" => options.runs = number_value(&arg, args.next())?,
+                    _ => rest.push(arg),
+                }
+            }
+            return Ok(Command::Bench(selection(rest.into_iter())?, options));
+        }
+        Some("interactive") => {
+            args.next();
+            return Ok(Command::Interactive(selection(args)?));
+        }
+        Some("tui") => {
+            args.next();
+            return Ok(Command::Tui(selection(args)?));
+        }
+        Some("edit") => {
+            args.next();
+            let mut options = edit::Options::default();
+            let mut rest = Vec::new();
+            for arg in args {
+                match arg.as_str() {
+                    "--reset" => options.reset = true,
+                    _ => rest.push(arg),
+                }
+            }
+            let mut selection = selection(rest.into_iter())?;
+            if selection.numbers.len() != 1 || selection.from.is_some() || selection.to.is_some() {
+                return Err(tr!(CLI_ONE_SECTION, "edit"));
+            }
+            return Ok(Command::Edit(selection.numbers.remove(0), options));
+        }
+        Some("errors") => {
+            args.next();
+            let (show, rest): (Vec<String>, Vec<String>) = args.partition(|arg| arg == "--show");
+            return Ok(Command::Errors(
+                selection(rest.into_iter())?,
+                !show.is_empty(),
+            ));
+        }
+        Some("check") => {
+            args.next();
+            return Ok(Command::Check(selection(args)?));
+        }
+        Some("watch") => {
+            args.next();
+            let rest: Vec<String> = args.collect();
+            let selection = selection(rest.clone().into_iter())?;
+            if selection.is_empty() {
+                return Err(tr!(CLI_SECTION_EXPECTED, "watch"));
+            }
+            return Ok(Command::Watch(selection, rest));
+        }
+        Some("verify") => {
+            args.next();
+            let (bless, args) = take_bless(args);
+            let selection = selection(args.into_iter())?;
+            if bless {
+                return Ok(Command::Bless(selection));
+            }
+            return Ok(Command::Verify(selection));
+        }
+        Some("lang-check") => return Ok(Command::LangCheck),
+        Some("-h" | "--help" | "help") => return Ok(Command::Help),
+        _ => {}
+    }
+    let (bless, args) = take_bless(args);
+    if bless {
+        return Ok(Command::Bless(selection(args.into_iter())?));
+    }
+
+    let mut options = runner::Options::default();
+    let mut rest = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--panics" => options.panics = true,
+            "--time" => options.time = true,
+            "--alloc" => options.alloc = true,
+            "--format" => match args.next().as_deref() {
+                Some("json") => options.json = true,
+                Some("text") => options.json = false,
+                _ => return Err(tr!(CLI_RUN_FORMAT).to_string()),
+            },
+            _ => rest.push(arg),
+        }
+    }
+    Ok(Command::Run(selection(rest.into_iter())?, options))
+}
+
+// `--bless` в любом месте командной строки превращает запуск или сверку в перезапись
+// аннотаций: `start --bless 5.1`, `start 5.1 --bless`, `start verify 5.1 --bless`.
+// Возвращает, был ли флаг, и остальные аргументы без него.
+fn take_bless<I>(args: I) -> (bool, Vec<String>)
+where
+    I: Iterator<Item = String>,
+{
+    let (bless, rest): (Vec<String>, Vec<String>) = args.partition(|arg| arg == "--bless");
+    (!bless.is_empty(), rest)
+}
+
+fn selection<I>(mut args: I) -> Result<Selection, String>
+where
+    I: Iterator<Item = String>,
+{
+    let mut selection = Selection::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--from" => selection.from = Some(number_arg(&arg, args.next())?),
+            "--to" => selection.to = Some(number_arg(&arg, args.next())?),
+            _ if arg.starts_with('-') => return Err(tr!(CLI_UNKNOWN_FLAG, arg)),
+            _ => selection.numbers.push(SectionNumber::parse(&arg)?),
+        }
+    }
+    Ok(selection)
+}
+
+fn number_value<T: std::str::FromStr>(flag: &str, value: Option<String>) -> Result<T, String> {
+    value
+        .and_then(|value| value.parse().ok())
+        .ok_or_else(|| tr!(CLI_NUMBER_EXPECTED, flag))
+}
+
+fn number_arg(flag: &str, value: Option<String>) -> Result<SectionNumber, String> {
+    let value = value.ok_or_else(|| tr!(CLI_SECTION_NUMBER_EXPECTED, flag))?;
+    SectionNumber::parse(&value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(args: &[&str]) -> Result<Command, String> {
+        parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    fn selection(args: &[&str]) -> Selection {
+        match parsed(args) {
+            Ok(Command::Run(selection, _)) => selection,
+            _ => panic!("`{}` должно разобраться в запуск разделов", args.join(" ")),
+        }
+    }
+
+    #[test]
+    fn parses_numbers_and_range() {
+        assert!(selection(&[]).is_empty());
+
+        let numbers = selection(&["8.5", "9.2.1"]);
+        assert_eq!(
+            numbers.numbers,
+            [
+                SectionNumber::parse("8.5").unwrap(),
+                SectionNumber::parse("9.2.1").unwrap()
+            ]
+        );
+
+        let range = selection(&["--from", "3", "--to", "5"]);
+        assert_eq!(range.from, Some(SectionNumber::parse("3").unwrap()));
+        assert_eq!(range.to, Some(SectionNumber::parse("5").unwrap()));
+    }
+
+    #[test]
+    fn parses_subcommands() {
+        assert!(matches!(parsed(&["--help"]), Ok(Command::Help)));
+        assert!(
+            matches!(parsed(&["toc", "8.5"]), Ok(Command::Toc(selection)) if !selection.is_empty())
+        );
+    }
+
+    #[test]
+    fn parses_run_options() {
+        match parsed(&["9.4", "--panics"]) {
+            Ok(Command::Run(selection, options)) => {
+                assert!(options.panics);
+                assert_eq!(selection.numbers, [SectionNumber::parse("9.4").unwrap()]);
+            }
+            _ => panic!("`9.4 --panics` должно разобраться в запуск разделов"),
+        }
+    }
+
+    #[test]
+    fn bless_can_be_anywhere() {
+        let bless = [
+            &["--bless", "5.1"][..],
+            &["5.1", "--bless"],
+            &["verify", "--bless", "5.1"],
+            &["verify", "5.1", "--bless"],
+        ];
+        for args in bless {
+            match parsed(args) {
+                Ok(Command::Bless(selection)) => {
+                    assert_eq!(selection.numbers, [SectionNumber::parse("5.1").unwrap()])
+                }
+                _ => panic!("`{}` должно разобраться в --bless", args.join(" ")),
+            }
+        }
+        assert!(matches!(parsed(&["verify", "5.1"]), Ok(Command::Verify(_))));
+    }
+
+    #[test]
+    fn rejects_bad_arguments() {
+        assert!(parsed(&["--from"]).is_err());
+        assert!(parsed(&["--verbose"]).is_err());
+        assert!(parsed(&["8.x"]).is_err());
+    }
+}
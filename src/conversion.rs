@@ -1,7 +1,12 @@
+//! Глава 6 «Преобразование»: `From` и `Into`, `TryFrom` и `TryInto`, преобразование в строку и из неё.
+
 // Атрибут, который убирает предупреждения компилятора о неиспользуемом коде
-#![allow(dead_code)]
 #![allow(unused_variables)]
-// 6. Преобразование
+
+use crate::book::Section;
+use crate::output::Output;
+
+// 6 Преобразование
 /*
 Примитивные типы могут быть сконвертированы в другие при помощи приведения типов.
 
@@ -16,9 +21,8 @@ Rust предоставляет преобразование между поль
 Если вы можете конвертировать тип А в тип В, то будет легко предположить, что мы должны быть в состоянии конвертировать тип В в тип А.
 */
 
+// 6.1.1 From
 /*
-From
-
 Типаж From позволяет типу определить, как он будет создаваться из другого типа, что предоставляет очень простой механизм конвертации между несколькими типами.
 Eсть несколько реализаций этот типажа в стандартной библиотеке для преобразования примитивов и общих типов.
 let my_str = "привет";
@@ -27,6 +31,7 @@ let my_string = String::from(my_str);
 
 use std::convert::From;
 
+#[allow(dead_code)]
 #[derive(Debug)]
 struct Number {
     value: i32,
@@ -38,26 +43,27 @@ impl From<i32> for Number {
     }
 }
 
-fn from() {
+/// Создание `Number` из `i32` через `From`.
+pub fn from(out: &Output) {
     let num = Number::from(30);
-    println!("Мой номер {:?}", num);
+    writeln!(out, "Мой номер {:?}", num);
     // >> Мой номер Number { value: 30 }
 }
 
+// 6.1.2 Into
 /*
-Into
-
 Трейт Into является полной противоположностью трейта From.
 Так что если вы реализовали для вашего типа трейт From, то трейт Into вызовет его при необходимости.
 Использование типажа Into обычно требует спецификации типа, в который мы собираемся конвертировать, так как компилятор чаще всего не может это вывести.
 Однако это небольшой компромисс, учитывая, что данную функциональность мы получаем бесплатно.
 */
 
-fn into() {
+/// Тот же `From`, вызванный через `into`.
+pub fn into(out: &Output) {
     let int = 5;
     let num: Number = int.into();
 
-    println!("Мой номер {:?}", num);
+    writeln!(out, "Мой номер {:?}", num);
     // >> Мой номер Number { value: 5 }
 }
 
@@ -70,8 +76,9 @@ fn into() {
 use std::convert::TryFrom;
 use std::convert::TryInto;
 
+/// Чётное число. Создавайте его через `EvenNumber::try_from`, который отвергает нечётные значения.
 #[derive(Debug, PartialEq)]
-struct EvenNumber(i32);
+pub struct EvenNumber(pub i32);
 
 impl TryFrom<i32> for EvenNumber {
     type Error = ();
@@ -85,7 +92,8 @@ impl TryFrom<i32> for EvenNumber {
     }
 }
 
-fn try_from_try_into() {
+/// Преобразование с проверкой: `TryFrom` и `TryInto` для чётного числа.
+pub fn try_from_try_into(out: &Output) {
     // TryFrom
     assert_eq!(EvenNumber::try_from(8), Ok(EvenNumber(8)));
     assert_eq!(EvenNumber::try_from(5), Err(()));
@@ -98,9 +106,8 @@ fn try_from_try_into() {
 }
 
 // 6.3 FromStr и ToString
+// 6.3.1 Конвертация в строку
 /*
-Конвертация в строку
-
 Преобразовать любой тип в String так же просто, как и реализовать для него типаж ToString.
 Вместо того, чтобы делать это напрямую, вы должны реализовать типаж fmt::Display,
     который автоматически предоставляет реализацию ToString, а также позволяет распечатать тип.
@@ -118,14 +125,16 @@ impl fmt::Display for Circle {
     }
 }
 
-fn to_string() {
+/// `to_string` для типа, который реализует `Display`.
+#[allow(clippy::to_string_in_format_args)]
+pub fn to_string(out: &Output) {
     let circle = Circle { radius: 6 };
-    println!("{}", circle.to_string());
+    writeln!(out, "{}", circle.to_string());
+    // >> Круг радиусом 6
 }
 
+// 6.3.2 Парсинг строки
 /*
-Парсинг строки
-
 Один из наиболее общих типов конвертации - это преобразование строки в число.
 Идиоматический подход это сделать при помощи функции parse и указания типа, в который будем преобразовывать,
     что можно сделать либо через выведение типа, либо при помощи 'turbofish'-синтаксиса.
@@ -135,20 +144,33 @@ fn to_string() {
 Чтобы получить эту функциональность для пользовательского типа, надо просто реализовать для этого типа типаж FromStr.
 */
 
-fn from_str() {
+/// Разбор строки в число через `parse`: с аннотацией и «турбо-рыбой».
+pub fn from_str(out: &Output) {
     let parsed: i32 = "5".parse().unwrap();
     let turbo_parsed = "10".parse::<i32>().unwrap();
 
     let sum = parsed + turbo_parsed;
-    println!("Сумма {:?}", sum)
-    // >> Круг радиусом 6
+    writeln!(out, "Сумма {:?}", sum)
+    // >> Сумма 15
 }
 
-pub fn run6() {
-    from();
-    into();
-    try_from_try_into();
-    to_string();
-    from_str();
-    // >> Сумма 15
+/// Глава целиком: все разделы подряд, без заголовков.
+pub fn run6(out: &Output) {
+    from(out);
+    into(out);
+    try_from_try_into(out);
+    to_string(out);
+    from_str(out);
 }
+
+/// Разделы главы 6 в порядке книги.
+pub const SECTIONS: &[Section] = &[
+    heading!("6", "Преобразование"),
+    heading!("6.1", "From и Into"),
+    section!("6.1.1", "From", from),
+    section!("6.1.2", "Into", into),
+    section!("6.2", "TryFrom и TryInto", try_from_try_into),
+    heading!("6.3", "FromStr и ToString"),
+    section!("6.3.1", "Конвертация в строку", to_string),
+    section!("6.3.2", "Парсинг строки", from_str),
+];
@@ -0,0 +1,129 @@
+// `start edit 5.1`: песочница для кода раздела.
+// Глава копируется во временный крейт, открывается в `$EDITOR`, после выхода из редактора
+// копия собирается и запускается, а её вывод (или ошибки компилятора) показывается рядом
+// с выводом оригинала. Так можно раскомментировать строки «Ошибка! ...», не меняя книгу.
+
+use std::env;
+use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::Path;
+use std::process::Command;
+
+use crate::book::Section;
+use crate::i18n;
+use crate::runner;
+use crate::scratch::{self, Scratch};
+use crate::tr;
+
+#[derive(Default)]
+pub struct Options {
+    // Начать заново с кода из книги, отбросив прошлые правки
+    pub reset: bool,
+}
+
+// Возвращает `false`, если изменённый код не собрался
+pub fn edit(section: &Section, options: &Options) -> Result<bool, String> {
+    let Some(run) = section.run else {
+        return Err(tr!(EDIT_NO_FUNCTION, section.number));
+    };
+    let scratch = Scratch::create(&format!("edit-{}", section.number))?;
+    let path = scratch.main_rs();
+    // Правки сохраняются между запусками `start edit` одного и того же раздела.
+    // Рядом записан хеш главы, из которой сделана копия: если глава потом изменилась
+    // (например, после `git pull`), копия собирается из старого кода, и об этом стоит сказать.
+    let hash_path = scratch.dir.join("book.hash");
+    let hash = book_hash(section);
+    let outdated = if options.reset || !path.exists() {
+        scratch.write(&scratch::program(section))?;
+        fs::write(&hash_path, &hash).map_err(|err| format!("{}: {}", hash_path.display(), err))?;
+        false
+    } else {
+        fs::read_to_string(&hash_path).ok().as_deref() != Some(hash.as_str())
+    };
+    if outdated {
+        println!("{}", tr!(EDIT_OUTDATED));
+        println!("{}", tr!(EDIT_RESET_HINT, section.number));
+    }
+    let program =
+        fs::read_to_string(&path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    let line = scratch::fn_line(&program, section);
+    println!("{}", tr!(EDIT_FILE, section.func(), path.display(), line));
+    open_editor(&path, line)?;
+
+    println!("{}", tr!(EDIT_BUILDING));
+    let build = scratch.build()?;
+    let original = runner::capture_run(run);
+
+    println!();
+    println!("=== {} {} ===", section.number, section.title());
+    println!("{}", tr!(EDIT_ORIGINAL));
+    print!("{}", original.output);
+    if let Some(panic) = &original.panic {
+        println!("{}", tr!(EXPORT_PANIC));
+        println!("{}", panic);
+    }
+
+    println!();
+    println!("{}", tr!(EDIT_EDITED));
+    if !build.success {
+        print!("{}", build.stderr);
+        println!("{}", tr!(EDIT_BUILD_FAILED));
+        println!("{}", tr!(EDIT_RESET_HINT, section.number));
+        return Ok(false);
+    }
+    let edited = scratch.run()?;
+    print!("{}", edited.stdout);
+    if !edited.success {
+        println!("{}", tr!(EXPORT_PANIC));
+        // Сообщение `panic!` печатает не `Output`, поэтому переводится здесь
+        for line in edited.stderr.trim_end().lines() {
+            println!("{}", i18n::output(line));
+        }
+    }
+
+    println!();
+    // Раздел, который паникует намеренно, совпадает с оригиналом, если копия тоже паникует
+    if edited.success == original.panic.is_none() && edited.stdout == original.output {
+        println!("{}", tr!(EDIT_SAME));
+    } else {
+        println!("{}", tr!(EDIT_DIFFERENT));
+    }
+    Ok(true)
+}
+
+// Хеш исходника главы. `DefaultHasher` может поменяться с версией Rust,
+// тогда предупреждение об изменённой главе появится один лишний раз
+fn book_hash(section: &Section) -> String {
+    let mut hasher = DefaultHasher::new();
+    section.source.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+// `$VISUAL` или `$EDITOR` (можно с аргументами, например `code --wait`), иначе `vi`
+fn open_editor(path: &Path, line: usize) -> Result<(), String> {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or("vi");
+    let mut command = Command::new(program);
+    command.args(words);
+    // Консольные редакторы понимают `+<строка>` и открывают файл сразу на функции раздела
+    let name = Path::new(program)
+        .file_stem()
+        .and_then(|name| name.to_str());
+    if matches!(
+        name,
+        Some("vi" | "vim" | "nvim" | "nano" | "emacs" | "micro")
+    ) {
+        command.arg(format!("+{}", line));
+    }
+    let status = command
+        .arg(path)
+        .status()
+        .map_err(|err| tr!(EDIT_EDITOR_FAILED, editor, err))?;
+    if !status.success() {
+        return Err(tr!(EDIT_EDITOR_FAILED, editor, status));
+    }
+    Ok(())
+}
@@ -0,0 +1,303 @@
+// `start errors`: каталог намеренных ошибок компиляции.
+// В главах много закомментированных строк «Ошибка! ...», которые нельзя запустить, как остальные примеры.
+// Для каждой строки из каталога копия главы (см. `scratch`) собирается с этой строкой,
+// раскомментированной в функции раздела, и компилятор должен ответить ошибкой с записанным кодом.
+// Так видно, что пример по-прежнему ломает сборку, а с `--show` — и чем именно.
+
+use crate::annotations;
+use crate::book::{self, Section, SectionNumber, Selection};
+use crate::scratch::{self, Scratch};
+use crate::tr;
+
+pub struct CompileError {
+    // Раздел, в функции которого записана строка
+    pub section: &'static str,
+    // Закомментированный код без `// `, как в исходнике; строки многострочного примера разделены `\n`
+    pub snippet: &'static str,
+    // Код ошибки rustc
+    pub code: &'static str,
+}
+
+pub const ERRORS: &[CompileError] = &[
+    #[cfg(feature = "ch-primitives")]
+    CompileError {
+        section: "2",
+        snippet: "mutable = true;",
+        code: "E0308",
+    },
+    #[cfg(feature = "ch-variable-bindings")]
+    CompileError {
+        section: "4.1",
+        snippet: "_immutable_binding +=1;",
+        code: "E0384",
+    },
+    #[cfg(feature = "ch-variable-bindings")]
+    CompileError {
+        section: "4.2",
+        snippet: "writeln!(out, \"outer short: {}\", short_living_binding);",
+        code: "E0425",
+    },
+    #[cfg(feature = "ch-variable-bindings")]
+    CompileError {
+        section: "4.3",
+        snippet: "writeln!(out, \"другая связь: {}\", another_binding);",
+        code: "E0381",
+    },
+    #[cfg(feature = "ch-variable-bindings")]
+    CompileError {
+        section: "4.4",
+        snippet: "_mutable_integer = 50;",
+        code: "E0384",
+    },
+    #[cfg(feature = "ch-types")]
+    CompileError {
+        section: "5.1",
+        snippet: "let integer: u8 = decimal;",
+        code: "E0308",
+    },
+    #[cfg(feature = "ch-types")]
+    CompileError {
+        section: "5.1",
+        snippet: "let character = decimal as char;",
+        code: "E0604",
+    },
+    #[cfg(feature = "ch-flow-control")]
+    CompileError {
+        section: "8.5.1.4",
+        snippet: "let Foo{y} = foo;",
+        code: "E0027",
+    },
+    #[cfg(feature = "ch-flow-control")]
+    CompileError {
+        section: "8.6",
+        snippet: "\
+if Foo::Bar == a {
+    // ^-- это вызовет ошибку компиляции. Используйте `if let` вместо этого.
+    writeln!(out, \"a is foobar\");
+}",
+        code: "E0369",
+    },
+    #[cfg(feature = "ch-functions")]
+    CompileError {
+        section: "9.1",
+        snippet: "rectangle.translate(1.0, 1.0);",
+        code: "E0596",
+    },
+    #[cfg(feature = "ch-functions")]
+    CompileError {
+        section: "9.1",
+        snippet: "pair.destroy(out);",
+        code: "E0382",
+    },
+    // Та же строка есть и внутри блочного комментария выше: раскомментируется последняя
+    #[cfg(feature = "ch-functions")]
+    CompileError {
+        section: "9.2.1",
+        snippet: "let _reborrow = &count;",
+        code: "E0502",
+    },
+    #[cfg(feature = "ch-functions")]
+    CompileError {
+        section: "9.2.1",
+        snippet: "consume();",
+        code: "E0382",
+    },
+    #[cfg(feature = "ch-functions")]
+    CompileError {
+        section: "9.2.1",
+        snippet: "writeln!(out, \"Количество элементов {} в векторе\", haystack.len());",
+        code: "E0382",
+    },
+];
+
+// Ошибки из выбранных разделов
+pub fn select(selection: &Selection) -> Vec<&'static CompileError> {
+    ERRORS
+        .iter()
+        .filter(|error| {
+            SectionNumber::parse(error.section).is_ok_and(|number| selection.matches(&number))
+        })
+        .collect()
+}
+
+// Возвращает `true`, если каждая строка по-прежнему не компилируется и с тем же кодом ошибки.
+// С `show` диагностика компилятора печатается для каждой строки, иначе — только при расхождении.
+pub fn errors(errors: &[&CompileError], show: bool) -> Result<bool, String> {
+    let scratch = Scratch::create("errors")?;
+    let mut confirmed = 0;
+    for error in errors {
+        let section = book::sections()
+            .into_iter()
+            .find(|section| section.number == error.section)
+            .expect("у каждой ошибки из каталога есть раздел в реестре");
+        let first_line = error.snippet.lines().next().unwrap_or_default();
+        let title = format!(
+            "{} {} ({}): {}",
+            section.number,
+            section.title(),
+            error.code,
+            first_line
+        );
+
+        let Some(program) = uncomment(&scratch::program(section), section, error.snippet) else {
+            println!("{}", tr!(STATUS_ERROR, title));
+            println!("    {}", tr!(ERRORS_NOT_FOUND, section.func()));
+            continue;
+        };
+        scratch.write(&program)?;
+        let build = scratch.check()?;
+        let codes = codes(&build.stderr);
+
+        let diagnostic = if build.success {
+            println!("{}", tr!(STATUS_ERROR, title));
+            println!("    {}", tr!(ERRORS_COMPILES));
+            show
+        } else if codes.iter().any(|code| code == error.code) {
+            confirmed += 1;
+            println!("{}", tr!(STATUS_OK, title));
+            show
+        } else {
+            println!("{}", tr!(STATUS_ERROR, title));
+            println!(
+                "    {}",
+                tr!(ERRORS_OTHER_CODE, error.code, codes.join(", "))
+            );
+            true
+        };
+        if diagnostic {
+            println!();
+            print!("{}", build.stderr);
+            println!();
+        }
+    }
+
+    println!();
+    println!("{}", tr!(ERRORS_TOTAL, confirmed, errors.len()));
+    Ok(confirmed == errors.len())
+}
+
+// Программа, в которой строки `snippet` в функции раздела раскомментированы.
+// Если строка встречается несколько раз, берётся последняя.
+fn uncomment(program: &str, section: &Section, snippet: &str) -> Option<String> {
+    let mut lines: Vec<String> = program.lines().map(str::to_string).collect();
+    let span = annotations::fn_span(program, section.func_name())?;
+    let snippet: Vec<&str> = snippet.lines().map(str::trim).collect();
+
+    let start = span.rev().find(|&start| {
+        snippet.iter().enumerate().all(|(i, expected)| {
+            lines.get(start + i).is_some_and(|line| {
+                let code = line.trim_start().strip_prefix("//");
+                code.is_some_and(|code| code.trim() == *expected)
+            })
+        })
+    })?;
+    for line in &mut lines[start..start + snippet.len()] {
+        let indent = line.len() - line.trim_start().len();
+        let code = &line[indent..];
+        let code = code
+            .strip_prefix("// ")
+            .or_else(|| code.strip_prefix("//"))
+            .unwrap_or(code);
+        *line = format!("{}{}", &line[..indent], code);
+    }
+    Some(lines.join("\n") + "\n")
+}
+
+// Коды ошибок `error[E0384]` из сообщений компилятора, без повторов
+fn codes(stderr: &str) -> Vec<String> {
+    let text = strip_colors(stderr);
+    let mut codes: Vec<String> = Vec::new();
+    for (start, _) in text.match_indices("error[E") {
+        let code: String = text[start + "error[".len()..]
+            .chars()
+            .take_while(|&c| c != ']')
+            .collect();
+        if !codes.contains(&code) {
+            codes.push(code);
+        }
+    }
+    codes
+}
+
+// Цветной вывод `cargo` в терминале разбивает `error[E0384]` escape-последовательностями
+fn strip_colors(text: &str) -> String {
+    let mut plain = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            plain.push(c);
+        }
+    }
+    plain
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROGRAM: &str = "\
+fn other() {
+    // let z = 1;
+}
+
+fn section() {
+    // let z = 1;
+    let y = 2;
+    if y > 1 {
+        //let x = 1;
+        //x = 3;
+    }
+    // let x = 1;
+}
+";
+
+    fn uncommented(snippet: &str) -> Option<String> {
+        let section = Section {
+            number: "1",
+            title: "",
+            path: "start::tests::section",
+            file: "src/tests.rs",
+            source: PROGRAM,
+            run: None,
+            should_panic: false,
+        };
+        uncomment(PROGRAM, &section, snippet)
+    }
+
+    // Отступ сохраняется, а пробел после `//` убирается вместе с ним
+    #[test]
+    fn keeps_the_indent() {
+        let program = uncommented("let x = 1;\nx = 3;").unwrap();
+        assert_eq!(
+            program,
+            PROGRAM.replace(
+                "//let x = 1;\n        //x = 3;",
+                "let x = 1;\n        x = 3;"
+            )
+        );
+        let program = uncommented("let z = 1;").unwrap();
+        assert!(program.contains("\n    let z = 1;\n    let y = 2;\n"));
+        // Та же строка в другой функции не тронута
+        assert!(program.starts_with("fn other() {\n    // let z = 1;\n"));
+    }
+
+    #[test]
+    fn uncomments_the_last_match() {
+        let program = uncommented("let x = 1;").unwrap();
+        assert!(program.contains("\n        //let x = 1;\n"));
+        assert!(program.ends_with("\n    let x = 1;\n}\n"));
+    }
+
+    #[test]
+    fn returns_none_when_the_snippet_is_missing() {
+        assert_eq!(uncommented("x = 4;"), None);
+        // Все строки фрагмента должны быть закомментированы
+        assert_eq!(uncommented("let z = 1;\nlet y = 2;"), None);
+    }
+}
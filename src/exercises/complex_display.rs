@@ -0,0 +1,20 @@
+// Задание к разделу 1.2.2 Формат Display.
+// Реализуйте `fmt::Display` для `Complex` так, чтобы число выводилось в виде `3.3 + 7.2i`.
+// Отрицательная мнимая часть выводится через минус: `0 - 1.5i`, а не `0 + -1.5i`.
+// Проверка решения: `start check 1.2.2`
+
+use std::fmt;
+
+// Поля читает решение, а нерешённая заготовка их не использует
+#[allow(dead_code)]
+pub struct Complex {
+    pub real: f32,
+    pub imag: f32,
+}
+
+impl fmt::Display for Complex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Заготовка: замените `?` действительной и мнимой частями
+        write!(f, "?")
+    }
+}
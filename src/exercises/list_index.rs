@@ -0,0 +1,21 @@
+// Задание к разделу 1.2.2.1 Пример форматирования Списка.
+// Дополните вывод каждого элемента его индексом: `[1: 0, 2: 1, 3: 2]`.
+// Проверка решения: `start check 1.2.2.1`
+
+use std::fmt;
+
+pub struct List(pub Vec<i32>);
+
+impl fmt::Display for List {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let vec = &self.0;
+        write!(f, "[")?;
+        for (count, v) in vec.iter().enumerate() {
+            if count != 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", v)?;
+        }
+        write!(f, "]")
+    }
+}
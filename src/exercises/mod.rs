@@ -0,0 +1,73 @@
+// Упражнения к разделам с пометкой `// Задание`.
+// Студент решает задание в заготовке из этого каталога, а `start check <номер>`
+// запускает проверку и сравнивает её вывод с ожидаемым, который в заготовке не виден.
+//
+// Задания вида "раскомментируйте строку и посмотрите на ошибку компиляции"
+// (например, `TODO` в `func::circuit_capture`) так проверить нельзя: они записаны
+// в каталоге `errors::ERRORS` и проверяются командой `start errors`.
+
+// Задания относятся к разделам главы 1 и собираются вместе с ней
+#[cfg(feature = "ch-formatting")]
+mod complex_display;
+#[cfg(feature = "ch-formatting")]
+mod list_index;
+
+use crate::i18n::Message;
+use crate::output::Output;
+
+pub struct Exercise {
+    // Раздел, к которому относится задание
+    pub section: &'static str,
+    pub title: Message,
+    // Файл с заготовкой, которую редактирует студент
+    pub file: &'static str,
+    // Печатает результаты кода студента на нескольких входных данных
+    pub check: fn(&Output),
+    // Ожидаемый вывод `check`
+    pub expected: &'static str,
+}
+
+pub const EXERCISES: &[Exercise] = &[
+    #[cfg(feature = "ch-formatting")]
+    Exercise {
+        section: "1.2.2",
+        title: crate::i18n::messages::EXERCISE_COMPLEX_DISPLAY,
+        file: "src/exercises/complex_display.rs",
+        check: check_complex_display,
+        expected: "\
+3.3 + 7.2i
+0 - 1.5i
+-2 + 0.5i",
+    },
+    #[cfg(feature = "ch-formatting")]
+    Exercise {
+        section: "1.2.2.1",
+        title: crate::i18n::messages::EXERCISE_LIST_INDEX,
+        file: "src/exercises/list_index.rs",
+        check: check_list_index,
+        expected: "\
+[1: 0, 2: 1, 3: 2]
+[10: 0, 100: 1, 1000: 2, 5433: 3, 0: 4, 10: 5]
+[]
+[-7: 0]",
+    },
+];
+
+#[cfg(feature = "ch-formatting")]
+fn check_complex_display(out: &Output) {
+    use complex_display::Complex;
+
+    for (real, imag) in [(3.3, 7.2), (0.0, -1.5), (-2.0, 0.5)] {
+        writeln!(out, "{}", Complex { real, imag });
+    }
+}
+
+#[cfg(feature = "ch-formatting")]
+fn check_list_index(out: &Output) {
+    use list_index::List;
+
+    writeln!(out, "{}", List(vec![1, 2, 3]));
+    writeln!(out, "{}", List(vec![10, 100, 1000, 5433, 0, 10]));
+    writeln!(out, "{}", List(vec![]));
+    writeln!(out, "{}", List(vec![-7]));
+}
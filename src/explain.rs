@@ -0,0 +1,113 @@
+// `start explain 5.1`: теория раздела, перенесённая по ширине терминала,
+// а по желанию ещё и исходный код раздела с ожидаемым выводом
+
+use std::env;
+
+use crate::annotations;
+use crate::book::Section;
+use crate::i18n;
+use crate::tr;
+
+#[derive(Default)]
+pub struct Options {
+    // Показать код функции раздела
+    pub source: bool,
+    // Показать ожидаемый вывод из аннотаций `// >>`
+    pub output: bool,
+}
+
+pub fn explain(sections: &[&Section], options: &Options) {
+    let width = terminal_width();
+    for section in sections {
+        println!("=== {} {} ===", section.number, section.title());
+
+        let theory = section.theory();
+        if theory.is_empty() {
+            println!("{}", tr!(EXPLAIN_NO_THEORY));
+        } else {
+            println!("{}", wrap(&theory, width));
+        }
+
+        if options.source {
+            if let Some(span) = annotations::fn_span(section.source, section.func_name()) {
+                println!();
+                println!(
+                    "{}",
+                    tr!(EXPLAIN_SOURCE, section.func(), section.file, span.start + 1)
+                );
+                for line in section.source.lines().skip(span.start).take(span.len()) {
+                    println!("{}", line);
+                }
+            }
+        }
+
+        if options.output && section.run.is_some() {
+            println!();
+            println!("{}", tr!(EXPLAIN_EXPECTED));
+            let expected = annotations::expected(section);
+            if expected.is_empty() {
+                println!("{}", tr!(EXPLAIN_NO_ANNOTATIONS));
+            }
+            for annotation in expected {
+                println!("{}", i18n::output(&annotation.text));
+            }
+        }
+        println!();
+    }
+}
+
+// Ширина терминала из переменной `COLUMNS`, которую выставляют большинство оболочек
+pub fn terminal_width() -> usize {
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse().ok())
+        .filter(|&columns| columns >= 20)
+        .unwrap_or(80)
+}
+
+// Переносит длинные строки по словам. Перенесённая часть получает отступ исходной строки,
+// поэтому списки вида `    по ссылке: &T` остаются выровненными.
+pub fn wrap(text: &str, width: usize) -> String {
+    let mut wrapped = Vec::new();
+    for line in text.lines() {
+        let indent = &line[..line.len() - line.trim_start().len()];
+        let mut current = indent.to_string();
+        for word in line.split_whitespace() {
+            let len = current.chars().count();
+            if len > indent.len() && len + 1 + word.chars().count() > width {
+                wrapped.push(current);
+                current = indent.to_string();
+            }
+            if current.len() > indent.len() {
+                current.push(' ');
+            }
+            current.push_str(word);
+        }
+        wrapped.push(current.trim_end().to_string());
+    }
+    wrapped.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Ширина считается в символах, а не в байтах: кириллица занимает по два байта
+    #[test]
+    fn wraps_cyrillic_by_characters() {
+        assert_eq!(wrap("раз два три", 7), "раз два\nтри");
+        assert_eq!(wrap("раз два три", 6), "раз\nдва\nтри");
+        assert_eq!(wrap("раз два три", 11), "раз два три");
+    }
+
+    #[test]
+    fn keeps_the_indent_of_wrapped_lines() {
+        assert_eq!(wrap("    по ссылке: &T", 14), "    по ссылке:\n    &T");
+    }
+
+    #[test]
+    fn leaves_long_words_and_empty_lines_alone() {
+        assert_eq!(wrap("деструктуризация", 5), "деструктуризация");
+        assert_eq!(wrap("а\n\nб", 80), "а\n\nб");
+    }
+}
@@ -0,0 +1,308 @@
+// `start export --format md|html --out DIR`: книга в виде страниц, по одной на главу.
+// На странице главы для каждого раздела — заголовок, теория, код функции и её фактический вывод.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use crate::annotations;
+use crate::book::{self, Section};
+use crate::i18n;
+use crate::runner;
+use crate::tr;
+
+#[derive(Clone, Copy)]
+pub enum Format {
+    Markdown,
+    Html,
+}
+
+impl Format {
+    pub fn parse(name: &str) -> Result<Format, String> {
+        match name {
+            "md" | "markdown" => Ok(Format::Markdown),
+            "html" => Ok(Format::Html),
+            _ => Err(tr!(EXPORT_UNKNOWN_FORMAT, name)),
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Format::Markdown => "md",
+            Format::Html => "html",
+        }
+    }
+}
+
+// Раздел вместе с тем, что удалось получить при его запуске
+struct Page<'a> {
+    section: &'a Section,
+    theory: String,
+    source: Option<String>,
+    output: Option<Result<String, String>>,
+}
+
+pub fn export(sections: &[&Section], format: Format, dir: &Path) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|err| format!("{}: {}", dir.display(), err))?;
+
+    // Разделы группируются по главам, заголовок главы берётся из реестра,
+    // даже если сама глава не попала в выбор
+    let mut chapters: Vec<(&Section, Vec<&Section>)> = Vec::new();
+    for section in sections {
+        let chapter = chapter_of(section);
+        match chapters.last_mut() {
+            Some((last, pages)) if last.number == chapter.number => pages.push(section),
+            _ => chapters.push((chapter, vec![section])),
+        }
+    }
+
+    let ext = format.extension();
+    let mut index = Vec::new();
+    for (chapter, sections) in &chapters {
+        let pages: Vec<Page> = sections.iter().map(|section| page(section)).collect();
+        let file = format!("chapter-{:0>2}.{}", chapter.number, ext);
+        let text = match format {
+            Format::Markdown => markdown_chapter(chapter, &pages),
+            Format::Html => html_chapter(chapter, &pages),
+        };
+        let path = dir.join(&file);
+        fs::write(&path, text).map_err(|err| format!("{}: {}", path.display(), err))?;
+        println!("{}", tr!(EXPORT_WRITTEN, path.display()));
+        index.push((*chapter, file));
+    }
+
+    let text = match format {
+        Format::Markdown => markdown_index(&index),
+        Format::Html => html_index(&index),
+    };
+    let path = dir.join(format!("index.{}", ext));
+    fs::write(&path, text).map_err(|err| format!("{}: {}", path.display(), err))?;
+    println!("{}", tr!(EXPORT_WRITTEN, path.display()));
+    Ok(())
+}
+
+fn chapter_of(section: &Section) -> &'static Section {
+    let chapter = section.number.split('.').next().unwrap_or_default();
+    book::sections()
+        .into_iter()
+        .find(|other| other.number == chapter)
+        .expect("у каждого раздела в реестре есть глава")
+}
+
+fn page(section: &Section) -> Page<'_> {
+    Page {
+        section,
+        theory: section.theory(),
+        source: source(section),
+        output: section.run.map(|_| runner::capture(section)),
+    }
+}
+
+// Код функции раздела; у заголовков глав без функции его нет
+pub fn source(section: &Section) -> Option<String> {
+    annotations::fn_span(section.source, section.func_name()).map(|span| {
+        let lines: Vec<&str> = section.source.lines().collect();
+        lines[span].join("\n")
+    })
+}
+
+fn markdown_index(chapters: &[(&Section, String)]) -> String {
+    let mut text = format!("# {}\n\n", tr!(EXPORT_CONTENTS));
+    for (chapter, file) in chapters {
+        writeln!(text, "- [{} {}]({})", chapter.number, chapter.title(), file).unwrap();
+    }
+    text
+}
+
+fn markdown_chapter(chapter: &Section, pages: &[Page]) -> String {
+    let mut text = format!("[{}](index.md)\n", tr!(EXPORT_CONTENTS));
+    // Если в выбор попали только подразделы, заголовок главы всё равно нужен
+    if pages[0].section.number != chapter.number {
+        writeln!(text, "\n# {} {}", chapter.number, chapter.title()).unwrap();
+    }
+    for page in pages {
+        let section = page.section;
+        let level = "#".repeat(section.depth().min(6));
+        writeln!(text, "\n{} {} {}\n", level, section.number, section.title()).unwrap();
+
+        if !page.theory.is_empty() {
+            // Отступы в теории превратили бы строки в блоки кода, поэтому строки
+            // выравниваются по левому краю и разделяются жёстким переносом
+            for line in page.theory.lines() {
+                let line = line.trim().replace('<', "\\<");
+                if line.is_empty() {
+                    text.push('\n');
+                } else {
+                    writeln!(text, "{}  ", line).unwrap();
+                }
+            }
+            text.push('\n');
+        }
+        if let Some(source) = &page.source {
+            writeln!(text, "```rust\n{}\n```\n", source).unwrap();
+        }
+        match &page.output {
+            Some(Ok(output)) if !output.is_empty() => {
+                let title = tr!(EXPORT_OUTPUT);
+                writeln!(text, "{}\n\n```text\n{}\n```", title, output.trim_end()).unwrap();
+            }
+            Some(Err(err)) => {
+                let title = tr!(EXPORT_PANIC);
+                writeln!(text, "{}\n\n```text\n{}\n```", title, err).unwrap();
+            }
+            _ => {}
+        }
+    }
+    text
+}
+
+const STYLE: &str = "\
+body { max-width: 60rem; margin: 2rem auto; padding: 0 1rem; font-family: sans-serif; line-height: 1.4; }
+.theory { white-space: pre-wrap; }
+pre { background: #f5f5f5; padding: 0.75rem; overflow-x: auto; }
+pre.output { background: #eef6ee; }
+pre.panic { background: #f8e8e8; }";
+
+pub fn html_page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{}</title>\n<style>\n{}\n</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        i18n::lang().code(),
+        escape(title),
+        STYLE,
+        body
+    )
+}
+
+fn html_index(chapters: &[(&Section, String)]) -> String {
+    let mut body = format!("<h1>{}</h1>\n<ul>\n", tr!(EXPORT_CONTENTS));
+    for (chapter, file) in chapters {
+        writeln!(
+            body,
+            "<li><a href=\"{}\">{} {}</a></li>",
+            file,
+            chapter.number,
+            escape(chapter.title())
+        )
+        .unwrap();
+    }
+    body.push_str("</ul>\n");
+    html_page(tr!(EXPORT_CONTENTS), &body)
+}
+
+fn html_chapter(chapter: &Section, pages: &[Page]) -> String {
+    let mut body = format!(
+        "<p><a href=\"index.html\">{}</a></p>\n",
+        tr!(EXPORT_CONTENTS)
+    );
+    if pages[0].section.number != chapter.number {
+        writeln!(
+            body,
+            "<h1>{} {}</h1>",
+            chapter.number,
+            escape(chapter.title())
+        )
+        .unwrap();
+    }
+    for page in pages {
+        let section = page.section;
+        let level = section.depth().min(6);
+        writeln!(
+            body,
+            "<h{0} id=\"{1}\">{1} {2}</h{0}>",
+            level,
+            section.number,
+            escape(section.title())
+        )
+        .unwrap();
+
+        if !page.theory.is_empty() {
+            writeln!(body, "<div class=\"theory\">{}</div>", escape(&page.theory)).unwrap();
+        }
+        if let Some(source) = &page.source {
+            writeln!(body, "<pre><code>{}</code></pre>", escape(source)).unwrap();
+        }
+        match &page.output {
+            Some(Ok(output)) if !output.is_empty() => {
+                writeln!(body, "<pre class=\"output\">{}</pre>", escape(output)).unwrap();
+            }
+            Some(Err(err)) => {
+                writeln!(body, "<pre class=\"panic\">{}</pre>", escape(err)).unwrap();
+            }
+            _ => {}
+        }
+    }
+    html_page(&format!("{} {}", chapter.number, chapter.title()), &body)
+}
+
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "ch-functions")]
+    fn section(number: &str) -> &'static Section {
+        book::sections()
+            .into_iter()
+            .find(|section| section.number == number)
+            .unwrap()
+    }
+
+    // Страница раздела 9.2.5 с заданными теорией, кодом и выводом
+    #[cfg(feature = "ch-functions")]
+    fn page(theory: &str, source: &str, output: Result<&str, &str>) -> Page<'static> {
+        Page {
+            section: section("9.2.5"),
+            theory: theory.to_string(),
+            source: Some(source.to_string()),
+            output: Some(output.map(str::to_string).map_err(str::to_string)),
+        }
+    }
+
+    #[test]
+    fn escapes_html() {
+        assert_eq!(
+            escape("<a href=\"x\">&T</a>"),
+            "&lt;a href=&quot;x&quot;&gt;&amp;T&lt;/a&gt;"
+        );
+    }
+
+    #[cfg(feature = "ch-functions")]
+    #[test]
+    fn html_escapes_theory_code_and_output() {
+        let pages = [page(
+            "Возвращает impl Fn<T> & co",
+            "fn create_fn() -> impl Fn() { || println!(\"<>\") }",
+            Ok("a: <Fn>"),
+        )];
+        let html = html_chapter(section("9"), &pages);
+        assert!(html.contains("<div class=\"theory\">Возвращает impl Fn&lt;T&gt; &amp; co</div>"));
+        assert!(html.contains("println!(&quot;&lt;&gt;&quot;)"));
+        assert!(html.contains("<pre class=\"output\">a: &lt;Fn&gt;</pre>"));
+        assert!(html.contains("<title>9 Функции</title>"));
+        // Выбраны только подразделы, поэтому заголовок главы добавлен отдельно
+        assert!(html.contains("<h1>9 Функции</h1>"));
+    }
+
+    #[cfg(feature = "ch-functions")]
+    #[test]
+    fn markdown_escapes_theory_but_not_code() {
+        let pages = [page(
+            "Тип Vec<T>:\n    с отступом",
+            "let v: Vec<u8> = Vec::new();",
+            Err("паника"),
+        )];
+        let markdown = markdown_chapter(section("9"), &pages);
+        // `<` в теории экранируется, а отступ не превращает строку в блок кода
+        assert!(markdown.contains("Тип Vec\\<T>:  \nс отступом  \n"));
+        assert!(markdown.contains("```rust\nlet v: Vec<u8> = Vec::new();\n```"));
+        assert!(markdown.contains("Раздел завершается паникой:\n\n```text\nпаника\n```"));
+        assert!(markdown.contains("\n### 9.2.5 Как выходные параметры\n"));
+    }
+}
@@ -1,6 +1,11 @@
+//! Глава 7 «Выражение»: инструкции, выражения и блоки как значения.
+
 #![allow(unused_must_use)]
 
-// 7. Выражение
+use crate::book::Section;
+use crate::output::Output;
+
+// 7 Выражение
 
 /*
 Программы на языке Rust - это (в основном) набор последовательных операторов:
@@ -31,7 +36,8 @@ fn main() {
 Однако, если последнее выражение в блоке оканчивается точкой с запятой, в качестве значения будет возвращено ().
 */
 
-fn expression() {
+/// Инструкции и выражения: блок как значение и точка с запятой в конце.
+pub fn expression(out: &Output) {
     let x = 5u32;
 
     let y = {
@@ -41,17 +47,22 @@ fn expression() {
         x_cube + x_squared + x
     };
 
+    #[allow(clippy::let_unit_value, clippy::no_effect)]
     let z = {
         2 * x;
     };
-    println!("x равен {:?}", x);
-    // >>  x равен 5
-    println!("y равен {:?}", y);
+    writeln!(out, "x равен {:?}", x);
+    // >> x равен 5
+    writeln!(out, "y равен {:?}", y);
     // >> y равен 155
-    println!("z равен {:?}", z);
+    writeln!(out, "z равен {:?}", z);
     // >> z равен ()
 }
 
-pub fn run7() {
-    expression();
+/// Глава целиком: все разделы подряд, без заголовков.
+pub fn run7(out: &Output) {
+    expression(out);
 }
+
+/// Разделы главы 7 в порядке книги.
+pub const SECTIONS: &[Section] = &[section!("7", "Выражение", expression)];
@@ -1,4 +1,10 @@
-// 9. Функции
+//! Глава 9 «Функции»: методы, замыкания, функции высшего порядка и расходящиеся функции.
+
+use crate::book::Section;
+use crate::output::Output;
+use crate::trace::Traced;
+
+// 9 Функции
 /*
 Функции объявляются с помощью ключевого слова `fn`.
 Их аргументы имеют явно заданный тип, как у переменных,
@@ -10,6 +16,7 @@
 */
 
 // Функция, которая возвращает булево значение
+#[allow(clippy::manual_is_multiple_of)]
 fn is_divisible_by(lhs: u32, rhs: u32) -> bool {
     // Особый случай, ранний возврат
     if rhs == 0 {
@@ -20,26 +27,28 @@ fn is_divisible_by(lhs: u32, rhs: u32) -> bool {
 }
 
 // Функции которые "не" возвращают значение, на самом деле возвращают единичный тип `()`
-fn fizzbuzz(n: u32) -> () {
+#[allow(clippy::unused_unit)]
+fn fizzbuzz(out: &Output, n: u32) -> () {
     if is_divisible_by(n, 15) {
-        println!("fizzbuz");
+        writeln!(out, "fizzbuz");
     } else if is_divisible_by(n, 3) {
-        println!("fizz");
+        writeln!(out, "fizz");
     } else if is_divisible_by(n, 5) {
-        println!("buzz");
+        writeln!(out, "buzz");
     } else {
-        println!("{}", n)
+        writeln!(out, "{}", n)
     }
 }
 // Если функция возвращает `()`, тип возвращаемого значения можно не указывать в сигнатуре
-fn fizzbuzz_to(n: u32) {
+fn fizzbuzz_to(out: &Output, n: u32) {
     for n in 1..=n {
-        fizzbuzz(n);
+        fizzbuzz(out, n);
     }
 }
 
-fn func() {
-    fizzbuzz_to(100);
+/// FizzBuzz, разбитый на функции, которые возвращают значения.
+pub fn func(out: &Output) {
+    fizzbuzz_to(out, 100);
 }
 
 // 9.1 Методы
@@ -50,9 +59,12 @@ fn func() {
 Методы — это связанные функции, которые вызываются для конкретного экземпляра типа.
 */
 
-struct Point {
-    x: f64,
-    y: f64,
+/// Точка на плоскости.
+pub struct Point {
+    /// Абсцисса.
+    pub x: f64,
+    /// Ордината.
+    pub y: f64,
 }
 
 // Блок реализации, все функции и методы, связанные с типом `Point` размещаются здесь
@@ -62,18 +74,23 @@ impl Point {
     Связанные функции не обязательно вызывать с каким-то экземпляром класса.
     Чаще всего такие функции используются как конструкторы.
     */
-    fn origin() -> Point {
+    /// Начало координат.
+    pub fn origin() -> Point {
         Point { x: 0.0, y: 0.0 }
     }
     // Ещё одны связанная функция, принимающая два аргумента:
-    fn new(x: f64, y: f64) -> Point {
+    /// Точка с координатами `(x, y)`.
+    pub fn new(x: f64, y: f64) -> Point {
         Point { x, y }
     }
 }
 
-struct Rectangle {
-    p1: Point,
-    p2: Point,
+/// Прямоугольник со сторонами, параллельными осям, заданный противоположными углами.
+pub struct Rectangle {
+    /// Один угол.
+    pub p1: Point,
+    /// Противоположный угол.
+    pub p2: Point,
 }
 
 impl Rectangle {
@@ -82,14 +99,16 @@ impl Rectangle {
     `&self` - это синтаксический сахар для замены `self: &Self`, где `Self` - это тип вызывающего объекта
     В данном случае `Self` = `Rectangle`
     */
-    fn area(&self) -> f64 {
+    /// Площадь прямоугольника.
+    pub fn area(&self) -> f64 {
         let Point { x: x1, y: y1 } = self.p1;
         let Point { x: x2, y: y2 } = self.p2;
         // `abs` - это метод, возвращающий переменную типа `f64`, равную абсолютному значению вызывающего объекта
         ((x1 - x2) * (y1 - y2)).abs()
     }
 
-    fn perimeter(&self) -> f64 {
+    /// Периметр прямоугольника.
+    pub fn perimeter(&self) -> f64 {
         let Point { x: x1, y: y1 } = self.p1;
         let Point { x: x2, y: y2 } = self.p2;
 
@@ -100,7 +119,8 @@ impl Rectangle {
     Этот метод требует, чтобы вызывающий объект был изменяемым
     `&mut self` преобразуется в  `self: &mut Self`
     */
-    fn translate(&mut self, x: f64, y: f64) {
+    /// Сдвигает прямоугольник на `(x, y)`.
+    pub fn translate(&mut self, x: f64, y: f64) {
         self.p1.x += x;
         self.p2.x += x;
 
@@ -109,20 +129,22 @@ impl Rectangle {
     }
 }
 
-// `Pair` владеет ресурсами: двумя целыми числами, память для которых выделена в куче
-struct Pair(Box<i32>, Box<i32>);
+// `Pair` владеет ресурсами: двумя целыми числами, память для которых выделена в куче.
+// `Traced` отмечает в хронике владения, когда эта память освобождается.
+struct Pair(Traced<Box<i32>>, Traced<Box<i32>>);
 
 impl Pair {
     /*
     Этот метод "потребляет" ресурсы вызывающего объекта `self` преобразуется в `self: Self`
     */
-    fn destroy(self) {
+    fn destroy(self, out: &Output) {
         let Pair(first, second) = self;
-        println!("Удаляем Pair({}, {})", first, second);
+        writeln!(out, "Удаляем Pair({}, {})", first, second);
     }
 }
 
-fn method() {
+/// Связанные функции и методы с `&self`, `&mut self` и `self`.
+pub fn method(out: &Output) {
     let rectangle = Rectangle {
         // Связанные функции вызываются с помощью двойных двоеточий
         p1: Point::origin(),
@@ -134,9 +156,9 @@ fn method() {
     Обратите внимание, что первый аргумент `&self` передаётся неявно, т.е.
         `rectangle.perimeter()` === `Rectangle::perimeter(&rectangle)`
     */
-    println!("Периметр прямоугольника: {}", rectangle.perimeter());
+    writeln!(out, "Периметр прямоугольника: {}", rectangle.perimeter());
     // >> Периметр прямоугольника: 14
-    println!("Площадь прямоугольника: {}", rectangle.area());
+    writeln!(out, "Площадь прямоугольника: {}", rectangle.area());
     // >> Площадь прямоугольника: 12
 
     let mut square = Rectangle {
@@ -150,11 +172,15 @@ fn method() {
     // Порядок! Изменяемые объекты могут вызывать изменяемые методы
     square.translate(1.0, 1.0);
 
-    let pair = Pair(Box::new(1), Box::new(2));
+    let pair = Pair(
+        Traced::new("first", Box::new(1)),
+        Traced::new("second", Box::new(2)),
+    );
 
-    pair.destroy();
+    pair.destroy(out);
+    // >> Удаляем Pair(1, 2)
     // Ошибка! Предыдущий вызов `destroy` "употребил" переменную `pair`
-    // pair.destroy();
+    // pair.destroy(out);
 }
 
 // 9.2 Замыкание
@@ -173,7 +199,8 @@ fn method() {
 
 */
 
-fn circuit() {
+/// Замыкания с аннотацией типов и с выводом типов.
+pub fn circuit(out: &Output) {
     // Инкремент с помощью замыкания и функции.
     fn funtion(i: i32) -> i32 {
         i + 1
@@ -190,15 +217,15 @@ fn circuit() {
 
     let i = 1;
 
-    println!("Функция: {}", funtion(i));
+    writeln!(out, "Функция: {}", funtion(i));
     // >> Функция: 2
-    println!("Замыкание с указанием типа: {}", closure_annotated(i));
+    writeln!(out, "Замыкание с указанием типа: {}", closure_annotated(i));
     // >> Замыкание с указанием типа: 2
-    println!("Замыкание с выводом типа: {}", closure_inferred(i));
+    writeln!(out, "Замыкание с выводом типа: {}", closure_inferred(i));
     // >> Замыкание с выводом типа: 2
 
     let one = || 1;
-    println!("Замыкание, возращает один: {}", one());
+    writeln!(out, "Замыкание, возращает один: {}", one());
     // >> Замыкание, возращает один: 1
 }
 
@@ -213,7 +240,8 @@ fn circuit() {
 Преимущественно, они захватывают переменные по ссылке, и используют другие способы только там, где это необходимо.
 */
 
-fn circuit_capture() {
+/// Захват переменных замыканием по ссылке, по изменяемой ссылке и по значению.
+pub fn circuit_capture(out: &Output) {
     use std::mem;
 
     let color = "green";
@@ -221,11 +249,11 @@ fn circuit_capture() {
     Замыкание для вывода `color`, которое немедленно заимствует (`&`)
         `color` и сохраняет замыкание в переменной `print`. `color` будет оставаться
     заимствованным до тех пор, пока `print` не будет использован в последний раз.
-    `println!` принимает аргументы только по неизменяемым ссылкам,
+    `writeln!` принимает аргументы только по неизменяемым ссылкам,
         поэтому он не накладываетдополнительных ограничений.
     */
 
-    let print = || println!("color: {}", color);
+    let print = || writeln!(out, "color: {}", color);
     // Вызывает замыкание, использующее заимствование.
     print();
     // >> color: green
@@ -248,7 +276,7 @@ fn circuit_capture() {
     */
     let mut inc = || {
         count += 1;
-        println!("count: {}", count)
+        writeln!(out, "count: {}", count)
     };
     for _ in 1..5 {
         /*
@@ -262,6 +290,12 @@ fn circuit_capture() {
         // Попытка перезанять приведёт к ошибке.
         // let _reborrow = &count;
     }
+    /*
+    >> count: 1
+    >> count: 2
+    >> count: 3
+    >> count: 4
+    */
 
     /*
     Замыкание больше не заимствует `&mut count`.
@@ -269,38 +303,41 @@ fn circuit_capture() {
     */
     let _count_reborrweb = &mut count;
 
-    // Некопируемый тип.
-    let movable = Box::new(3);
+    // Некопируемый тип. `Traced` покажет в хронике владения, когда он будет удалён
+    let movable = Traced::new("movable", Box::new(3));
 
     /*
     `mem::drop` требует `T`, так что захват производится по значению.
     Копируемый тип будет скопирован в замыкание, оставив оригинальное значение без изменения.
     Некопируемый тип должен быть перемещён, так что movable` немедленно перемещается в замыкание.
     */
+    movable.moved_into("consume");
     let consume = || {
-        println!("moveble: {:?}", movable);
+        writeln!(out, "moveble: {:?}", movable);
         mem::drop(movable);
     };
     // `consume` поглощает переменную, так что оно может быть вызвано только один раз.
     consume();
+    // >> moveble: 3
     // Ошибка так как переменной `movable` уже не сущществует
     // consume();
-    // println!("{:?}", movable);
+    // writeln!(out, "{:?}", movable);
 
     /*
     Использование move перед вертикальными линиями позволяет получить владение над захваченными переменными:
     */
-    let haystack = vec![1, 2, 3];
+    let haystack = Traced::new("haystack", vec![1, 2, 3]);
 
+    haystack.moved_into("contains");
     let contains = move |needle| haystack.contains(needle);
-    println!("{}", contains(&1));
+    writeln!(out, "{}", contains(&1));
     // >> true
-    println!("{}", contains(&7));
+    writeln!(out, "{}", contains(&7));
     // >> false
 
     // !Ошибка `move` передало владение в функцию. `haystack` больше не существует
-    // println!("Количество элементов {} в векторе", haystack.len());
-    // >> Количество элементов 3 в векторе
+    // writeln!(out, "Количество элементов {} в векторе", haystack.len());
+    // Без `move` здесь было бы выведено: Количество элементов 3 в векторе
 }
 
 // 9.2.2 Как входные параметры
@@ -342,7 +379,8 @@ where
     f(3)
 }
 
-fn input_param_circuit() {
+/// Замыкания как параметры функций, ограниченные `Fn`, `FnMut` и `FnOnce`.
+pub fn input_param_circuit(out: &Output) {
     use std::mem;
 
     let greeting = "привет";
@@ -352,12 +390,12 @@ fn input_param_circuit() {
     // Захват двух переменных: `greeting` по ссылке и `farewell` по значению.
     let diary = || {
         // `greeting` захватывается по ссылке: требует `Fn`.
-        println!("Я сказал {}.", greeting);
+        writeln!(out, "Я сказал {}.", greeting);
         // Изменяемость требует от `farewell` быть захваченным по изменяемой ссылке. Сейчас требуется `FnMut`.
         farawell.push_str("!!!");
 
-        println!("Потом я закричал {}.", farawell);
-        println!("Теперь я могу поспать. zzzzz");
+        writeln!(out, "Потом я закричал {}.", farawell);
+        writeln!(out, "Теперь я могу поспать. zzzzz");
 
         // Ручной вызов удаления требуется от `farewell` быть захваченным по значению. Теперь требуется `FnOnce`.
         mem::drop(farawell);
@@ -367,7 +405,7 @@ fn input_param_circuit() {
 
     // `double` удовлетворяет ограничениям типажа `apply_to_3`
     let double = |x| 2 * x;
-    println!("Удвоенное 3: {}", apply_to_3(double));
+    writeln!(out, "Удвоенное 3: {}", apply_to_3(double));
 }
 
 // 9.2.3 Анонимность типов
@@ -399,10 +437,11 @@ where
 }
 */
 
-fn annotated_circuit() {
+/// Замыкание, переданное в обобщённую функцию с ограничением `Fn`.
+pub fn annotated_circuit(out: &Output) {
     let x = 7;
     // Захватываем `x` в анонимный тип и реализуем `Fn` для него. Сохраняем его как `print`.
-    let print = || println!("{}", x);
+    let print = || writeln!(out, "{}", x);
     apply(print);
 }
 
@@ -418,17 +457,20 @@ fn call_me<F: Fn()>(f: F) {
     f();
 }
 
-// Объявляем функцию-обёртку, удовлетворяющую ограничению `Fn`
-fn function() {
-    println!("Я функция!");
+// Объявляем функцию, которая печатает в `out`
+fn function(out: &Output) {
+    writeln!(out, "Я функция!");
 }
 
-fn output_func_circuit() {
+/// Обычная функция там, где ожидается замыкание.
+pub fn output_func_circuit(out: &Output) {
     // Определяем замыкание, удовлетворяющее ограничению `Fn`
-    let closure = || println!("Я замыкание");
+    let closure = || writeln!(out, "Я замыкание");
 
     call_me(closure);
-    call_me(function);
+    // Функции `out` передаётся аргументом, поэтому её вызов оборачиваем в замыкание,
+    // которое захватывает `out` и удовлетворяет ограничению `Fn`
+    call_me(|| function(out));
     // Стоит отметить, что типажи Fn, FnMut и FnOnce указывают, как замыкание захватывает переменные из своей области видимости.
 }
 
@@ -444,32 +486,36 @@ fn output_func_circuit() {
 Это необходимо, так как любые захваченные по ссылке значения будут удалены после выхода из функции, оставляя недопустимые ссылки в замыкании.
 */
 
-fn create_fn() -> impl Fn() {
+// `out` — сама ссылка, её `move` копирует в замыкание. `use<'a, 'w>` говорит, что замыкание
+// живёт, пока живы `out` и поток, в который он пишет
+
+fn create_fn<'a, 'w>(out: &'a Output<'w>) -> impl Fn() + use<'a, 'w> {
     let text = "Fn".to_owned();
-    move || println!("a: {}", text)
+    move || writeln!(out, "a: {}", text)
 }
 
-fn create_fnmut() -> impl FnMut() {
+fn create_fnmut<'a, 'w>(out: &'a Output<'w>) -> impl FnMut() + use<'a, 'w> {
     let text = "FnMut".to_owned();
-    move || println!("a: {}", text)
+    move || writeln!(out, "a: {}", text)
 }
 
-fn create_fnonce() -> impl FnOnce() {
+fn create_fnonce<'a, 'w>(out: &'a Output<'w>) -> impl FnOnce() + use<'a, 'w> {
     let text = "FnOnce".to_owned();
-    move || println!("a: {}", text)
+    move || writeln!(out, "a: {}", text)
 }
 
-fn output_params_circuit() {
-    let fn_plain = create_fn();
-    let mut fn_mut = create_fnmut();
-    let fn_once = create_fnonce();
+/// Функции, которые возвращают замыкания через `impl Fn`.
+pub fn output_params_circuit(out: &Output) {
+    let fn_plain = create_fn(out);
+    let mut fn_mut = create_fnmut(out);
+    let fn_once = create_fnonce(out);
 
     fn_plain();
     fn_mut();
     fn_once();
 }
 
-// 9.2.6 Примеры замыканий из стандарной библиотеке std
+// 9.2.6 Примеры замыканий из стандартной библиотеки std
 // 9.2.6.1 Iterator::any
 /*
 Iterator::any - это функция, которая принимает итератор и возвращает true, если любой элемент удовлетворяет предикату. Иначе возвращает false.
@@ -494,40 +540,47 @@ pub trait Iterator {
 }
 */
 
-fn example_iterator_any() {
+/// `Iterator::any` для векторов и массивов.
+#[allow(clippy::manual_contains)]
+pub fn example_iterator_any(out: &Output) {
+    #[allow(clippy::useless_vec)]
     let vec1 = vec![1, 2, 3];
     let vec2 = vec![4, 5, 6];
 
     // `iter()` для векторов даёт `&i32`. Приводим к `i32`.
-    println!("2 в vec1: {}", vec1.iter().any(|&x| x == 2));
+    writeln!(out, "2 в vec1: {}", vec1.iter().any(|&x| x == 2));
     // >> 2 в vec1: true
 
     // `into_iter()` для векторов даёт `i32`. Приведения не требуется.
-    println!("2 в vec2: {}", vec2.into_iter().any(|x| x == 2));
+    writeln!(out, "2 в vec2: {}", vec2.into_iter().any(|x| x == 2));
     // >> 2 в vec2: false
 
     let array1 = [1, 2, 3];
     let array2 = [4, 5, 6];
 
     // `iter()` для массивов даёт `&i32`.
-    println!("2 в array1: {}", array1.iter().any(|&x| x == 2));
+    writeln!(out, "2 в array1: {}", array1.iter().any(|&x| x == 2));
     // >> 2 в array1: true
 
     // `into_iter()` для массивов даёт `i32`. Приведения не требуется.
-    println!("2 в array2: {}", array2.into_iter().any(|x| x == 2));
+    writeln!(out, "2 в array2: {}", array2.into_iter().any(|x| x == 2));
     // >> 2 в array2: false
 }
 
 // 9.2.6.2 Поиск через итераторы
 
+/// Сигнатура `find` из трейта `Iterator` стандартной библиотеки. Раздел вызывает
+/// настоящий `std::iter::Iterator`, а этот трейт только показывает его объявление.
 pub trait Iterator {
     // Тип, по которому выполняется итерирование.
+    /// Тип элементов.
     type Item;
 
     /*
     `find` принимает `&mut self`, что означает,
     что вызывающий объект может быть заимствован и изменён, но не поглощён.
     */
+    /// Первый элемент, для которого `predicate` вернул `true`.
     fn find<P>(&mut self, predicate: P) -> Option<Self::Item>
     where
         /*
@@ -538,7 +591,9 @@ pub trait Iterator {
         P: FnMut(&Self::Item) -> bool;
 }
 
-fn example_iterator_find() {
+/// `Iterator::find` и `position` для векторов и массивов.
+pub fn example_iterator_find(out: &Output) {
+    #[allow(clippy::useless_vec)]
     let vec1 = vec![1, 2, 3];
     let vec2 = vec![4, 5, 6];
 
@@ -551,25 +606,30 @@ fn example_iterator_find() {
     `iter()` для векторов выдаёт `&i32`, а мы хотим ссылаться на один из его элементов,
     поэтому нам нужно деструктурировать `&&i32` в `i32`
     */
-    println!("Найдём 2 в vec1: {:?}", iter.find(|&&x| x == 2));
+    writeln!(out, "Найдём 2 в vec1: {:?}", iter.find(|&&x| x == 2));
     // >> Найдём 2 в vec1: Some(2)
 
     /*
     `into_iter()` для векторов выдаёт `i32`, а мы хотим ссылаться на один из его элементов,
     поэтому нам нужно деструктурировать `&i32` в `i32`
     */
-    println!("Найдём 2 в vec2: {:?}", into_iter.find(|&x| x == 2));
+    writeln!(out, "Найдём 2 в vec2: {:?}", into_iter.find(|&x| x == 2));
     // >> Найдём 2 в vec2: None
 
     let array1 = [1, 2, 3];
     let array2 = [4, 5, 6];
 
     // `iter()` для массивов выдаёт `&i32`
-    println!("Find 2 in array1: {:?}", array1.iter().find(|&&x| x == 2));
+    writeln!(
+        out,
+        "Find 2 in array1: {:?}",
+        array1.iter().find(|&&x| x == 2)
+    );
     // >> Find 2 in array1: Some(2)
 
     // `into_iter()` для массивов выдаёт `i32`
-    println!(
+    writeln!(
+        out,
         "Find 2 in array2: {:?}",
         array2.into_iter().find(|&x| x == 2)
     );
@@ -603,11 +663,11 @@ fn is_odd(n: u32) -> bool {
     n % 2 == 1
 }
 
-fn func_fvp() {
-    println!("Найти сумму всех квадватов нечётных чисел не больше 1000");
-    let upper = 1000;
-
-    // Императивный подход. Объявляем переменную-накопитель
+/// Императивный вариант раздела 9.3: сумма нечётных квадратов меньше `upper` циклом.
+/// Отдельная функция, чтобы `start bench 9.3` замерял каждый стиль сам по себе.
+#[doc(hidden)]
+pub fn sum_of_squared_odd_imperative(upper: u32) -> u32 {
+    // Объявляем переменную-накопитель
     let mut acc = 0;
 
     // Итерируем: 0, 1, 2, ... до бесконечности
@@ -622,16 +682,36 @@ fn func_fvp() {
             acc += n_squared;
         }
     }
-    println!("Императивный стиль: {}", acc);
-    // >> Императивный стиль: 5456
+    acc
+}
 
-    // Функциональный подход
-    let sum_of_squared_odd_numbers: u32 = (0..)
+/// Функциональный вариант раздела 9.3: та же сумма цепочкой адаптеров итератора.
+#[doc(hidden)]
+pub fn sum_of_squared_odd_functional(upper: u32) -> u32 {
+    (0..)
         .map(|n| n * n) // Все натуральные числа возводим в квадрат
         .take_while(|&n_squared| n_squared < upper) // Берём те, что ниже верхнего предела
         .filter(|&n_squared| is_odd(n_squared)) // Выбираем нечётные
-        .sum(); // Складываем
-    println!("Функциональный стиль: {}", sum_of_squared_odd_numbers);
+        .sum() // Складываем
+}
+
+/// Сумма нечётных квадратов циклом и цепочкой адаптеров итератора.
+pub fn func_fvp(out: &Output) {
+    writeln!(
+        out,
+        "Найти сумму всех квадватов нечётных чисел не больше 1000"
+    );
+    // >> Найти сумму всех квадватов нечётных чисел не больше 1000
+    let upper = 1000;
+
+    // Императивный подход: цикл с переменной-накопителем
+    let acc = sum_of_squared_odd_imperative(upper);
+    writeln!(out, "Императивный стиль: {}", acc);
+    // >> Императивный стиль: 5456
+
+    // Функциональный подход: ленивый итератор без изменяемого состояния
+    let sum_of_squared_odd_numbers = sum_of_squared_odd_functional(upper);
+    writeln!(out, "Функциональный стиль: {}", sum_of_squared_odd_numbers);
     // >> Функциональный стиль: 5456
 }
 
@@ -652,32 +732,38 @@ fn foo() -> ! {
     panic!("Этот вызов никогда не вернёт управление.")
 }
 
+#[allow(clippy::unused_unit)]
 fn some_fn() {
     ()
 }
 
-fn divergent() {
+/// Расходящаяся функция с типом `!` и `continue` в ветви `match`.
+pub fn divergent(out: &Output) {
+    #[allow(clippy::let_unit_value, unused_variables)]
     let a = some_fn();
-    println!("Эта функция возращает управление и вы можете увидет эту строку");
+    writeln!(
+        out,
+        "Эта функция возращает управление и вы можете увидет эту строку"
+    );
     // >> Эта функция возращает управление и вы можете увидет эту строку
 
-    // Произойдёт передача управления в фунцию, но обратно оно уже не вернётся
+    // Произойдёт передача управления в фунцию, но обратно оно уже не вернётся (см. раздел 9.4.1)
     // let x = foo();
-    // println!("вы никогда не увидете эту строку");
+    // writeln!(out, "вы никогда не увидете эту строку");
 
     fn sum_odd_numbers(up_to: u32) -> u32 {
         let mut acc = 0;
         for i in 0..up_to {
             /*
             Обратите внимание, что возвращаемый тип этого выражения match должен быть u32
-                потому что такой тип в переменной "addition" . 
+                потому что такой тип в переменной "addition" .
             */
             let addition: u32 = match i % 2 == 1 {
                 true => i,
                 /*
                 С другой стороны выражение "continue" не возвращает
                 u32, но это тоже нормально, потому что это тип не возвращающий управление,
-                не нарушает требования к типу выражения match. 
+                не нарушает требования к типу выражения match.
                 */
                 false => continue,
             };
@@ -685,28 +771,63 @@ fn divergent() {
         }
         acc
     }
-    println!(
+    writeln!(
+        out,
         "Сумма нечётных чисел до 9 (исключая): {}",
         sum_odd_numbers(9)
     );
     // >> Сумма нечётных чисел до 9 (исключая): 16
     /*
-    Это также возвращаемый тип функций, которые содержат вечный цикл (например, loop {}), 
+    Это также возвращаемый тип функций, которые содержат вечный цикл (например, loop {}),
         как сетевые серверы или функции, завершающие процесс (например, exit()).
     */
 }
 
-pub fn run9() {
-    func();
-    method();
-    circuit();
-    circuit_capture();
-    input_param_circuit();
-    annotated_circuit();
-    output_func_circuit();
-    output_params_circuit();
-    example_iterator_any();
-    example_iterator_find();
-    func_fvp();
-    divergent();
-}
+// 9.4.1 Вызов расходящейся функции
+// Раздел намеренно завершается паникой, поэтому `start` запускает его только с флагом `--panics`
+/// Вызов расходящейся функции, которая паникует.
+pub fn divergent_call(out: &Output) {
+    writeln!(out, "Вызываем foo()");
+    // `!` приводится к любому типу, поэтому результат можно присвоить переменной типа `u32`
+    #[allow(clippy::diverging_sub_expression)]
+    let _x: u32 = foo();
+}
+
+/// Глава целиком: все разделы подряд, без заголовков.
+pub fn run9(out: &Output) {
+    func(out);
+    method(out);
+    circuit(out);
+    circuit_capture(out);
+    input_param_circuit(out);
+    annotated_circuit(out);
+    output_func_circuit(out);
+    output_params_circuit(out);
+    example_iterator_any(out);
+    example_iterator_find(out);
+    func_fvp(out);
+    divergent(out);
+}
+
+/// Разделы главы 9 в порядке книги.
+pub const SECTIONS: &[Section] = &[
+    section!("9", "Функции", func),
+    section!("9.1", "Методы", method),
+    section!("9.2", "Замыкание", circuit),
+    section!("9.2.1", "Захват", circuit_capture),
+    section!("9.2.2", "Как входные параметры", input_param_circuit),
+    section!("9.2.3", "Анонимность типов", annotated_circuit),
+    section!("9.2.4", "Входные функции", output_func_circuit),
+    section!("9.2.5", "Как выходные параметры", output_params_circuit),
+    heading!("9.2.6", "Примеры замыканий из стандартной библиотеки std"),
+    section!("9.2.6.1", "Iterator::any", example_iterator_any),
+    section!("9.2.6.2", "Поиск через итераторы", example_iterator_find),
+    section!("9.3", "Функции высшего порядка", func_fvp),
+    section!("9.4", "Расходящиеся функции", divergent),
+    section!(
+        "9.4.1",
+        "Вызов расходящейся функции",
+        divergent_call,
+        should_panic
+    ),
+];
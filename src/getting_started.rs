@@ -1,14 +1,19 @@
-// Атрибут, который убирает предупреждения компилятора о неиспользуемом коде
-#![allow(dead_code)]
-#![allow(unused_variables)]
+//! Глава 1 «Привет, мир!»: комментарии и форматированный вывод через `Debug` и `Display`.
+
+use crate::book::Section;
+use crate::output::Output;
+
 // 1 Привет, мир!
 // Эта строка — комментарий, она будет проигнорирована компилятором
-fn hello_world() {
+/// Первая программа: выводит «Привет, мир!».
+pub fn hello_world(out: &Output) {
     // Следующий код будет исполнен в момент, когда будет запущен исполняемый файл
     // println! - это макрос, который отображает текст в консоли.
-    println!("Привет, мир!");
+    // Здесь вместо него используется writeln!, который пишет текст в `out`:
+    // так вывод раздела можно показать в консоли или сохранить для проверки.
+    writeln!(out, "Привет, мир!");
     // >> Привет, мир!
-    println!("Я программирую на языке Rust!")
+    writeln!(out, "Я программирую на языке Rust!")
     // >> Я программирую на языке Rust!
 }
 
@@ -22,7 +27,8 @@ Doc комментарии, которые будут сгенерированы
     //! - Генерация документации для модуля.
 */
 
-fn comments() {
+/// Обычные и документирующие комментарии.
+pub fn comments(out: &Output) {
     // Это пример комментария к строке. В начале строки две косые чер
     // И ничего написанного внутри них не будет прочитано компилятором.
     // println!("Hello, world!");
@@ -40,10 +46,10 @@ fn comments() {
     // чем с комментариями к строке.
 
     let x = 5 + /* 90 +*/ 5;
-    println!("Is 'x' 10 or 100? x = {}", x);
+    writeln!(out, "Is 'x' 10 or 100? x = {}", x);
     // >> Is 'x' 10 or 100? x = 10
-    // Eсли раскоментировать /*90 + */
-    // >> Is 'x' 10 or 100? x = 100
+    // Eсли раскоментировать /*90 + */, то будет выведено:
+    // Is 'x' 10 or 100? x = 100
 }
 
 // 1.2 Форматированый вывод
@@ -55,26 +61,30 @@ fn comments() {
     println! - Aналогично print!, но в конце добавляется переход на новую строку.
     eprint! - Aналогично format!, но текст выводится в стандартный поток ошибок (io::stderr).
     eprintln! - Aналогично eprint!, но в конце добавляется переход на новую строку
+    write!, writeln! - Aналогично print! и println!, но текст записывается в переданный первым аргументом поток.
 */
 
-fn formating() {
+/// Макросы форматирования: позиционные и именованные аргументы, ширина, выравнивание.
+#[allow(clippy::write_literal, clippy::approx_constant)]
+pub fn formating(out: &Output) {
     // `{}` автоматически будет заменено на
     // аргументы. Они будут преобразованы в строку.
 
-    println!("{} дней", 31);
+    writeln!(out, "{} дней", 31);
     // >> 31 дней
 
     // Без суффиксов, 31 является i32. Можно изменить тип 31, используя суффикс.
-    println!("{} дней", 31i8);
+    writeln!(out, "{} дней", 31i8);
     // >> 31 дней
 
     // Существует множество способов работы с форматированным выводом. Можно указать
     // позицию для каждого аргумента.
-    println!("{0}, это {1}. {1}, это {0}", "Aлиса", "Боб");
-    // Aлиса, это Боб. Боб, это Aлиса
+    writeln!(out, "{0}, это {1}. {1}, это {0}", "Aлиса", "Боб");
+    // >> Aлиса, это Боб. Боб, это Aлиса
 
     // Так же можно именовать аргументы.
-    println!(
+    writeln!(
+        out,
         "{subject} {verb} {object}",
         object = "леннивую собаку",
         subject = "быстрая коричневая лиса",
@@ -82,22 +92,23 @@ fn formating() {
     );
     // >> быстрая коричневая лиса прыгает через леннивую собаку
 
-    println!(
+    writeln!(
+        out,
         "{} из {:b} людей знают, что такое двоичный код, a остальные нет.",
         1, 2
     );
     // >> 1 из 10 людей знают, что такое двоичный код, a остальные нет.
 
     // Можно выравнивать текст, сдвигая его на указанную ширину.
-    println!("{number:>width$}", number = 1, width = 6);
+    writeln!(out, "{number:>width$}", number = 1, width = 6);
     // >>      1
 
     // Можно добавить к цифрам пару нулей. Данный макрос выведет "000001".
-    println!("{number:0>width$}", number = 1, width = 6);
+    writeln!(out, "{number:0>width$}", number = 1, width = 6);
     // >> 000001
 
     // Округление дробного числа
-    println!("{:.3}", 3.141592f32);
+    writeln!(out, "{:.3}", 3.141592f32);
     // >> 3.142
 }
 
@@ -119,34 +130,43 @@ std::fmt содержит в себе много типажей, которые
 
 // Атрибут `derive` автоматически реализует необходимые методы,
 // чтобы была возможность напечатать структуру `struct` с помощью `fmt::Debug`.
+#[allow(dead_code)]
 #[derive(Debug)]
 struct DebugPrintable(i32);
 
+#[allow(dead_code)]
 #[derive(Debug)]
 struct Deep(DebugPrintable);
 
+#[allow(dead_code)]
 #[derive(Debug)]
 struct Person<'a> {
     name: &'a str,
     age: u8,
 }
-fn formating_debug() {
+/// Вывод через `#[derive(Debug)]`: `{:?}` и красивый `{:#?}`.
+pub fn formating_debug(out: &Output) {
     // Вывод с помощью `{:?}` DEBUG аналогичен `{}`.
-    println!("{:?} месяцев в году", 12i8);
+    writeln!(out, "{:?} месяцев в году", 12i8);
     // >> 12 месяцев в году
 
-    println!(
+    writeln!(
+        out,
         "{1:?} {0:?} - это имя {actor:?}.",
         "Слейтер",
         "Кристиан",
         actor = "актёра"
     );
-    // >> Кристиан" "Слейтер" - это имя "актёра".
+    // >> "Кристиан" "Слейтер" - это имя "актёра".
 
-    println!("Теперь {:?} будет выведена на экран!", DebugPrintable(8));
+    writeln!(
+        out,
+        "Теперь {:?} будет выведена на экран!",
+        DebugPrintable(8)
+    );
     // >> Теперь DebugPrintable(8) будет выведена на экран!
 
-    println!("A теперь напечатаем {:?}", Deep(DebugPrintable(7)));
+    writeln!(out, "A теперь напечатаем {:?}", Deep(DebugPrintable(7)));
     // >> A теперь напечатаем Deep(DebugPrintable(7))
 
     /*
@@ -154,7 +174,8 @@ fn formating_debug() {
         Rust также обеспечивает "красивую печать" с помощью {:#?}.
     */
 
-    println!(
+    writeln!(
+        out,
         "{:#?}",
         Person {
             name: "Peter",
@@ -165,10 +186,11 @@ fn formating_debug() {
     >> Person {
         name: "Peter",
         age: 27,
-     */
+    }
+    */
 }
 
-//  1.2.2 Формат Disply
+// 1.2.2 Формат Display
 /*
 fmt::Debug выглядит не очень компактно и красиво, поэтому полезно настраивать внешний вид информации, которая будет напечатана.
 Это можно сделать реализовав типаж fmt::Display вручную, который использует маркер {} для печати.
@@ -179,6 +201,7 @@ use core::str;
 // Импортируем (с помощью `use`) модуль `fmt`, чтобы мы могли его использовать.
 use std::fmt;
 
+#[allow(dead_code)]
 struct Structure(i32);
 
 // Чтобы была возможность использовать маркер `{}`
@@ -227,19 +250,21 @@ impl fmt::Display for Point2D {
     }
 }
 
-fn formating_display() {
+/// Ручная реализация `fmt::Display` для своей структуры.
+pub fn formating_display(out: &Output) {
     let minmax = MinMax(0, 14);
-    println!("Сравниваем форматирование:");
+    writeln!(out, "Сравниваем форматирование:");
     // >> Сравниваем форматирование:
-    println!("Display: {}", minmax);
-    // >> Display: (0, 14
-    println!("Debug: {:?}", minmax);
+    writeln!(out, "Display: {}", minmax);
+    // >> Display: (0, 14)
+    writeln!(out, "Debug: {:?}", minmax);
     // >> Debug: MinMax(0, 14)
 
     let big_range = MinMax(-300, 300);
     let small_range = MinMax(-3, 3);
 
-    println!(
+    writeln!(
+        out,
         "Большой диапозон - {big} и маленький диапозон {small}",
         small = small_range,
         big = big_range
@@ -247,24 +272,24 @@ fn formating_display() {
     // >> Большой диапозон - (-300, 300) и маленький диапозон (-3, 3)
 
     let point = Point2D { x: 3.3, y: 7.2 };
-    println!("Сравниваем форматирование:");
+    writeln!(out, "Сравниваем форматирование:");
     // >> Сравниваем форматирование:
-    println!("Display: {}", point);
+    writeln!(out, "Display: {}", point);
     // >> Display: x: 3.3, y: 7.2
-    println!("Debug: {:?}", point);
+    writeln!(out, "Debug: {:?}", point);
     // >> Debug: Point2D { x: 3.3, y: 7.2 }
 
-    // Задание
+    // Задание: решите его в src/exercises/complex_display.rs и проверьте командой `start check 1.2.2`
     let complex = Complex {
         real: 3.3,
         imag: 7.2,
     };
-    println!("Сравниваем форматирование:");
+    writeln!(out, "Сравниваем форматирование:");
     // >> Сравниваем форматирование:
-    println!("Display: {}", complex);
+    writeln!(out, "Display: {}", complex);
     // >> Display: 3.3 + 7.2i
-    println!("Debug: {:?}", complex);
-    // >> Complex { real: 3.3, imag: 7.2 }
+    writeln!(out, "Debug: {:?}", complex);
+    // >> Debug: Complex { real: 3.3, imag: 7.2 }
 }
 
 // 1.2.2.1 Пример форматирования Списка
@@ -278,7 +303,8 @@ fn formating_display() {
 // Было определено выше
 // use std::fmt;
 // // Определяем структуру с именем `List`, которая хранит в себе `Vec`
-struct List(Vec<i32>);
+/// Список чисел, который выводится через `Display` как `[1: 0, 2: 1]`: значение и его индекс.
+pub struct List(pub Vec<i32>);
 
 impl fmt::Display for List {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -295,6 +321,7 @@ impl fmt::Display for List {
             }
             // write!(f, "{}", v)?;
             // Задание. Дополнить вывод индекса
+            // (решите его в src/exercises/list_index.rs и проверьте командой `start check 1.2.2.1`)
             write!(f, "{}: {}", v, count)?;
         }
         // Закроем открытую скобку и вернём значение `fmt::Result`
@@ -302,15 +329,16 @@ impl fmt::Display for List {
     }
 }
 
-fn formating_list() {
+/// `Display` для списка: оператор `?` после каждого `write!` и индекс элемента.
+pub fn formating_list(out: &Output) {
     let v = List(vec![1, 2, 3]);
-    println!("{}", v);
-    // >> [1, 2, 3]
+    writeln!(out, "{}", v);
+    // До задания выводилось: [1, 2, 3]
     // Задание. Дополнить вывод индекса
     // >> [1: 0, 2: 1, 3: 2]
 
-    println!("{}", List(vec![10, 100, 1000, 5433, 0, 10]));
-    // >> [10, 100, 1000, 5433, 0, 10]
+    writeln!(out, "{}", List(vec![10, 100, 1000, 5433, 0, 10]));
+    // До задания выводилось: [10, 100, 1000, 5433, 0, 10]
     // Задание. Дополнить вывод индекса
     // >> [10: 0, 100: 1, 1000: 2, 5433: 3, 0: 4, 10: 5]
 }
@@ -328,10 +356,14 @@ fn formating_list() {
 Наиболее распространённый типаж для форматирования — Display, который работает без аргументов: например {}.
 */
 
-struct City {
-    name: &'static str,
-    lat: f32,
-    lon: f32,
+/// Город с координатами; `Display` печатает широту и долготу с полушариями: `Осло: 59.950°N 10.750°E`.
+pub struct City {
+    /// Название.
+    pub name: &'static str,
+    /// Широта в градусах, к северу положительная.
+    pub lat: f32,
+    /// Долгота в градусах, к востоку положительная.
+    pub lon: f32,
 }
 
 impl fmt::Display for City {
@@ -352,11 +384,15 @@ impl fmt::Display for City {
     }
 }
 
+/// Цвет RGB; `Display` печатает компоненты и шестнадцатеричный код: `RGB(128, 255, 90) 0x80ff5a`.
 #[derive(Debug)]
-struct Color {
-    red: u8,
-    green: u8,
-    blue: u8,
+pub struct Color {
+    /// Красная компонента.
+    pub red: u8,
+    /// Зелёная компонента.
+    pub green: u8,
+    /// Синяя компонента.
+    pub blue: u8,
 }
 
 impl fmt::Display for Color {
@@ -369,7 +405,9 @@ impl fmt::Display for Color {
     }
 }
 
-fn formating_formating() {
+/// Форматирование своих типов: координаты города и цвет в шестнадцатеричном виде.
+pub fn formating_formating(out: &Output) {
+    #[allow(clippy::excessive_precision)]
     for city in [
         City {
             name: "Дублин",
@@ -389,7 +427,7 @@ fn formating_formating() {
     ]
     .iter()
     {
-        println!("{}", *city);
+        writeln!(out, "{}", *city);
         /*
         >> Дублин: 59.348°N 6.260°W
         >> Осло: 59.950°N 10.750°E
@@ -415,28 +453,37 @@ fn formating_formating() {
     ]
     .iter()
     {
-        println!("{:?}", *color);
+        writeln!(out, "{:?}", *color);
+        writeln!(out, "{}", *color);
         /*
         >> Color { red: 128, green: 255, blue: 90 }
-        >> Color { red: 0, green: 3, blue: 254 }
-        >> Color { red: 0, green: 0, blue: 0 }
-        */
-        println!("{}", *color);
-        /*
         >> RGB(128, 255, 90) 0x80ff5a
+        >> Color { red: 0, green: 3, blue: 254 }
         >> RGB(0, 3, 254) 0x0003fe
+        >> Color { red: 0, green: 0, blue: 0 }
         >> RGB(0, 0, 0) 0x000000
         */
     }
 }
 
-// Вызов
-pub fn run1() {
-    hello_world();
-    comments();
-    formating();
-    formating_debug();
-    formating_display();
-    formating_list();
-    formating_formating();
+/// Глава целиком: все разделы подряд, без заголовков.
+pub fn run1(out: &Output) {
+    hello_world(out);
+    comments(out);
+    formating(out);
+    formating_debug(out);
+    formating_display(out);
+    formating_list(out);
+    formating_formating(out);
 }
+
+/// Разделы главы 1 в порядке книги.
+pub const SECTIONS: &[Section] = &[
+    section!("1", "Привет, мир!", hello_world),
+    section!("1.1", "Комментарии", comments),
+    section!("1.2", "Форматированый вывод", formating),
+    section!("1.2.1", "Формат DEBUG", formating_debug),
+    section!("1.2.2", "Формат Display", formating_display),
+    section!("1.2.2.1", "Пример форматирования Списка", formating_list),
+    section!("1.2.3", "Форматирование", formating_formating),
+];
@@ -0,0 +1,75 @@
+// Заголовки и теория разделов на текущем языке.
+// Русский текст — это сами исходники глав: заголовок из реестра и комментарии-теория.
+// Английский хранится в `book.txt` по номерам разделов; если перевода нет, показывается русский.
+
+use std::sync::OnceLock;
+
+use super::Lang;
+use crate::book::Section;
+
+// Английский текст одного раздела из `book.txt`
+pub struct Entry {
+    pub number: &'static str,
+    pub title: &'static str,
+    pub theory: String,
+}
+
+pub fn title(section: &Section) -> &'static str {
+    match super::lang() {
+        Lang::Ru => section.title,
+        Lang::En => entry(section.number).map_or(section.title, |entry| entry.title),
+    }
+}
+
+pub fn theory(section: &Section) -> String {
+    if super::lang() == Lang::En {
+        if let Some(entry) = entry(section.number) {
+            return entry.theory.clone();
+        }
+    }
+    crate::theory::theory(section)
+}
+
+pub fn entry(number: &str) -> Option<&'static Entry> {
+    entries().iter().find(|entry| entry.number == number)
+}
+
+pub fn entries() -> &'static [Entry] {
+    static ENTRIES: OnceLock<Vec<Entry>> = OnceLock::new();
+    ENTRIES.get_or_init(|| parse(include_str!("book.txt")))
+}
+
+// Разделы вида `=== 9.2.1 Capturing ===`, за каждым — его теория.
+// Строки до первого раздела — комментарии к файлу.
+fn parse(text: &'static str) -> Vec<Entry> {
+    let mut entries: Vec<Entry> = Vec::new();
+    let mut theory: Vec<&str> = Vec::new();
+    for line in text.lines() {
+        let heading = line
+            .strip_prefix("=== ")
+            .and_then(|line| line.strip_suffix(" ==="))
+            .and_then(|heading| heading.split_once(' '));
+        if let Some((number, title)) = heading {
+            if let Some(last) = entries.last_mut() {
+                last.theory = join(&theory);
+            }
+            theory.clear();
+            entries.push(Entry {
+                number,
+                title,
+                theory: String::new(),
+            });
+        } else if !entries.is_empty() {
+            theory.push(line);
+        }
+    }
+    if let Some(last) = entries.last_mut() {
+        last.theory = join(&theory);
+    }
+    entries
+}
+
+// Теория без пустых строк по краям, как у `theory::theory`
+fn join(lines: &[&str]) -> String {
+    lines.join("\n").trim_matches('\n').to_string()
+}
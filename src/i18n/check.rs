@@ -0,0 +1,225 @@
+// `start lang-check`: у каждой записи каталогов должны быть оба перевода.
+// Проверяются сообщения программы, заголовки и теория разделов и строки вывода примеров:
+// каждая русская строка из `write!`, `writeln!` и `panic!` в главах должна быть в `output.txt`.
+// В конце все разделы запускаются по-английски, и кириллицы в их выводе остаться не должно.
+
+use std::collections::BTreeMap;
+
+use super::messages::MESSAGES;
+use super::{book, output, with_lang, Lang};
+use crate::book::Section;
+use crate::runner;
+use crate::tr;
+
+#[derive(Default)]
+struct Report {
+    errors: usize,
+    warnings: usize,
+}
+
+impl Report {
+    fn error(&mut self, message: String) {
+        self.errors += 1;
+        println!("{}", tr!(STATUS_ERROR, message));
+    }
+
+    fn warning(&mut self, message: String) {
+        self.warnings += 1;
+        println!("{}", tr!(LANG_CHECK_WARNING, message));
+    }
+}
+
+// Возвращает `true`, если ошибок нет; предупреждения об устаревших записях проверку не валят
+pub fn check(sections: &[&Section]) -> bool {
+    let mut report = Report::default();
+    check_messages(&mut report);
+    check_sections(sections, &mut report);
+    check_output(sections, &mut report);
+    check_run(sections, &mut report);
+
+    println!();
+    println!(
+        "{}",
+        tr!(
+            LANG_CHECK_TOTAL,
+            MESSAGES.len(),
+            sections.len(),
+            output::phrases().len(),
+            report.errors,
+            report.warnings
+        )
+    );
+    report.errors == 0
+}
+
+fn check_messages(report: &mut Report) {
+    for message in MESSAGES {
+        for (lang, text) in [("ru", message.ru), ("en", message.en)] {
+            if text.trim().is_empty() {
+                report.error(tr!(LANG_CHECK_EMPTY, message.id, lang));
+            }
+        }
+        if message.ru.matches("{}").count() != message.en.matches("{}").count() {
+            report.error(tr!(LANG_CHECK_PLACEHOLDERS, message.id));
+        }
+    }
+}
+
+fn check_sections(sections: &[&Section], report: &mut Report) {
+    for section in sections {
+        let Some(entry) = book::entry(section.number) else {
+            report.error(tr!(LANG_CHECK_NO_TITLE, section.number, section.title));
+            continue;
+        };
+        let theory = crate::theory::theory(section);
+        if !theory.is_empty() && entry.theory.is_empty() {
+            report.error(tr!(LANG_CHECK_NO_THEORY, section.number, section.title));
+        } else if theory.is_empty() && !entry.theory.is_empty() {
+            report.warning(tr!(LANG_CHECK_EXTRA_THEORY, section.number, section.title));
+        }
+    }
+
+    // Разделы отключённых глав (`--no-default-features`) устаревшими не считаются
+    let chapter = |number: &str| number.split('.').next().unwrap_or_default().to_string();
+    for entry in book::entries() {
+        let in_book = sections
+            .iter()
+            .any(|section| section.number == entry.number);
+        let chapter_in_book = sections
+            .iter()
+            .any(|section| chapter(section.number) == chapter(entry.number));
+        if !in_book && chapter_in_book {
+            report.warning(tr!(LANG_CHECK_UNKNOWN_SECTION, entry.number));
+        }
+    }
+}
+
+fn check_output(sections: &[&Section], report: &mut Report) {
+    let mut files: BTreeMap<&str, &str> = BTreeMap::new();
+    for section in sections {
+        files.insert(section.file, section.source);
+    }
+
+    for phrase in output::phrases() {
+        if phrase.holes() != output::holes(phrase.en) {
+            report.error(tr!(LANG_CHECK_PATTERN_HOLES, phrase.ru));
+        }
+    }
+
+    for (file, source) in files {
+        let literals = literals(source);
+        for (line, text) in &literals {
+            let translated = output::phrases().iter().any(|phrase| phrase.ru == text);
+            if output::has_cyrillic(text) && !translated {
+                report.error(tr!(LANG_CHECK_NO_PATTERN, file, line + 1, text));
+            }
+        }
+        for phrase in output::phrases()
+            .iter()
+            .filter(|phrase| phrase.group == file)
+        {
+            if !literals.iter().any(|(_, text)| text == phrase.ru) {
+                report.warning(tr!(LANG_CHECK_UNUSED_PATTERN, file, phrase.ru));
+            }
+        }
+    }
+}
+
+// Вывод, который остался русским: не хватает шаблона или значения в группе `values`
+fn check_run(sections: &[&Section], report: &mut Report) {
+    for section in sections {
+        let output = with_lang(Lang::En, || runner::capture(section)).unwrap_or_else(|err| err);
+        for line in output.lines().filter(|line| output::has_cyrillic(line)) {
+            report.error(tr!(
+                LANG_CHECK_UNTRANSLATED,
+                section.number,
+                section.title,
+                line
+            ));
+        }
+    }
+}
+
+// Строки форматирования `write!(out, "...")`, `writeln!(out, "...")` и `panic!("...")`
+// вместе с номером строки; закомментированные вызовы пропускаются
+fn literals(source: &str) -> Vec<(usize, String)> {
+    let mut literals = Vec::new();
+    for call in ["write!(", "writeln!(", "panic!("] {
+        for (start, _) in source.match_indices(call) {
+            let before = &source[..start];
+            let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+            let prefix = &source[line_start..start];
+            if prefix.trim_start().starts_with("//")
+                || prefix.ends_with(|c: char| c.is_alphanumeric() || c == '_')
+            {
+                continue;
+            }
+            let mut rest = &source[start + call.len()..];
+            if call != "panic!(" {
+                let Some(comma) = first_argument_end(rest) else {
+                    continue;
+                };
+                rest = &rest[comma + 1..];
+            }
+            if let Some(text) = string_literal(rest.trim_start()) {
+                literals.push((before.matches('\n').count(), text));
+            }
+        }
+    }
+    literals.sort();
+    literals
+}
+
+// Запятая после первого аргумента макроса, например `out`
+fn first_argument_end(args: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (i, c) in args.char_indices() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' if depth == 0 => return None,
+            ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+// Значение строкового литерала в начале `code`, с разобранными escape-последовательностями
+fn string_literal(code: &str) -> Option<String> {
+    let mut chars = code.strip_prefix('"')?.chars();
+    let mut text = String::new();
+    loop {
+        match chars.next()? {
+            '"' => return Some(text),
+            '\\' => match chars.next()? {
+                'n' => text.push('\n'),
+                't' => text.push('\t'),
+                'r' => text.push('\r'),
+                '0' => text.push('\0'),
+                'u' => {
+                    let code: String = chars.by_ref().skip(1).take_while(|&c| c != '}').collect();
+                    text.push(char::from_u32(u32::from_str_radix(&code, 16).ok()?)?);
+                }
+                // Перенос строки после `\` вместе с отступом следующей строки
+                '\n' => {
+                    let rest = chars.as_str().trim_start();
+                    chars = rest.chars();
+                }
+                c => text.push(c),
+            },
+            c => text.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // То же, что `start lang-check`: новые сообщения и строки вывода нельзя добавить без перевода
+    #[test]
+    fn catalogs_have_no_errors() {
+        assert!(check(&crate::book::sections()));
+    }
+}
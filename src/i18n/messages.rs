@@ -0,0 +1,312 @@
+// Сообщения программы `start` на обоих языках.
+// Идентификатор — имя константы: `tr!(RUN_TOTAL, passed, panicked, skipped)`.
+// В тексте `{}` по порядку заменяются аргументами, поэтому в переводе их должно быть столько же.
+
+use super::Message;
+
+macro_rules! messages {
+    ($($id:ident: $ru:literal, $en:literal;)*) => {
+        $(
+            pub const $id: Message = Message {
+                id: stringify!($id),
+                ru: $ru,
+                en: $en,
+            };
+        )*
+
+        // Все сообщения каталога: их перебирает `start lang-check`
+        pub const MESSAGES: &[Message] = &[$($id),*];
+    };
+}
+
+messages! {
+    USAGE: "\
+Использование:
+    start                       запустить всю книгу
+    start <номер>...            запустить разделы, например `start 8.5 9.2.1`
+    start --from <номер> --to <номер>
+                                запустить диапазон разделов, например `start --from 3 --to 5`
+    start --panics ...          запустить и разделы, которые намеренно завершаются паникой
+    start --format json ...     вместо вывода напечатать отчёт о запуске в JSON
+    start --time ...            напечатать время выполнения каждого раздела
+    start --alloc ...           напечатать, сколько раз раздел выделял и освобождал память в куче
+    start --lang ru|en ...      язык вывода; без флага выбирается по `LANG`
+    start list|toc [<номер>...] показать оглавление книги или её части
+    start bench [--warmup <n>] [--runs <n>] [<номер>...]
+                                замерить время разделов: минимум, медиана и максимум,
+                                без номеров — циклы fizzbuzz (8.3, 8.4) и функции высшего порядка (9.3)
+    start quiz [--choices] [--count <n>] [--seed <n>] [<номер>...]
+                                викторина «что напечатает этот код?» по выбранным разделам
+    start progress              показать прогресс по главам и раздел, с которого продолжить;
+                                прогресс хранится в `~/.config/start`, `START_PROGRESS=off` — не сохранять его
+    start search <запрос>       найти разделы по заголовку, теории или коду, например `start search while let`
+    start explain <номер>... [--source] [--output]
+                                теория раздела, а также его код и ожидаемый вывод
+    start export --format md|html --out <каталог> [<номер>...]
+                                сохранить книгу страницами по главам: теория, код и вывод
+    start serve [--port <n>] [<номер>...]
+                                открыть книгу в браузере на http://127.0.0.1:<n>/ (по умолчанию 8080):
+                                оглавление, теория, код и вывод разделов с кнопкой запуска
+    start interactive [<номер>...]
+                                пройти разделы по шагам: теория, запуск, вывод
+    start tui [<номер>...]      полноэкранный просмотр: дерево разделов, теория, код и вывод, поиск по `/`
+    start edit <номер> [--reset]
+                                поправить код раздела в `$EDITOR` во временной копии, собрать и запустить её
+                                рядом с оригиналом; `--reset` — начать заново с кода из книги
+    start check [<номер>...]    проверить решения заданий из `src/exercises`, например `start check 1.2.2`
+    start watch <номер>...      пересобирать и запускать разделы после каждого сохранения файлов в `src/`
+    start errors [--show] [<номер>...]
+                                убедиться, что закомментированные строки «Ошибка!» по-прежнему не компилируются
+                                с ожидаемым кодом ошибки; `--show` — показать сообщения компилятора
+    start verify [<номер>...]   сверить вывод разделов с аннотациями `// >>` в исходниках
+    start --bless [<номер>...]  переписать аннотации `// >>` по фактическому выводу
+                                (то же, что `start verify --bless`)
+    start lang-check            проверить, что у всех сообщений, заголовков, теории
+                                и строк вывода есть русский и английский переводы",
+        "\
+Usage:
+    start                       run the whole book
+    start <number>...           run sections, e.g. `start 8.5 9.2.1`
+    start --from <number> --to <number>
+                                run a range of sections, e.g. `start --from 3 --to 5`
+    start --panics ...          also run the sections that panic on purpose
+    start --format json ...     print a JSON report of the run instead of the output
+    start --time ...            print how long each section took
+    start --alloc ...           print how many times each section allocated and freed heap memory
+    start --lang ru|en ...      output language; without the flag it is taken from `LANG`
+    start list|toc [<number>...]
+                                show the table of contents of the book or a part of it
+    start bench [--warmup <n>] [--runs <n>] [<number>...]
+                                time sections: minimum, median and maximum,
+                                without numbers the fizzbuzz loops (8.3, 8.4) and higher order functions (9.3)
+    start quiz [--choices] [--count <n>] [--seed <n>] [<number>...]
+                                a \"what does this code print?\" quiz on the selected sections
+    start progress              show progress by chapter and the section to continue with;
+                                progress is kept in `~/.config/start`, `START_PROGRESS=off` disables saving it
+    start search <query>        find sections by title, theory or code, e.g. `start search while let`
+    start explain <number>... [--source] [--output]
+                                the theory of a section, and optionally its code and expected output
+    start export --format md|html --out <dir> [<number>...]
+                                save the book as one page per chapter: theory, code and output
+    start serve [--port <n>] [<number>...]
+                                browse the book at http://127.0.0.1:<n>/ (8080 by default):
+                                contents, theory, code and output of sections with a run button
+    start interactive [<number>...]
+                                step through sections: theory, run, output
+    start tui [<number>...]     full-screen browser: section tree, theory, code and output, `/` to search
+    start edit <number> [--reset]
+                                edit the code of a section in `$EDITOR` in a temporary copy, build and run it
+                                next to the original; `--reset` starts over from the code in the book
+    start check [<number>...]   check the exercise solutions in `src/exercises`, e.g. `start check 1.2.2`
+    start watch <number>...     rebuild and run sections every time a file in `src/` is saved
+    start errors [--show] [<number>...]
+                                make sure the commented-out \"Error!\" lines still fail to compile
+                                with the expected error code; `--show` prints the compiler messages
+    start verify [<number>...]  compare the output of sections with the `// >>` annotations in the sources
+    start --bless [<number>...] rewrite the `// >>` annotations from the actual output
+                                (same as `start verify --bless`)
+    start lang-check            check that all messages, titles, theory
+                                and output strings have Russian and English translations";
+
+    // Общие сообщения и разбор командной строки
+    ERROR: "Ошибка: {}", "Error: {}";
+    NOTHING_SELECTED: "ни один раздел не подходит под выбор", "no section matches the selection";
+    NO_EXERCISES: "к выбранным разделам нет заданий", "the selected sections have no exercises";
+    LANG_UNKNOWN: "неизвестный язык `{}`, ожидается ru или en", "unknown language `{}`, expected ru or en";
+    LANG_EXPECTED: "после `--lang` ожидается ru или en", "`--lang` expects ru or en";
+    CLI_QUERY_EXPECTED: "`search` ожидает строку запроса", "`search` expects a query";
+    CLI_SECTION_EXPECTED: "`{}` ожидает номер раздела", "`{}` expects a section number";
+    CLI_EXPORT_FORMAT: "после `--format` ожидается md или html", "`--format` expects md or html";
+    CLI_EXPORT_OUT: "после `--out` ожидается каталог", "`--out` expects a directory";
+    CLI_EXPORT_DIR: "`export` ожидает `--out <каталог>`", "`export` expects `--out <dir>`";
+    CLI_RUN_FORMAT: "после `--format` ожидается text или json", "`--format` expects text or json";
+    CLI_UNKNOWN_FLAG: "неизвестный флаг `{}`", "unknown flag `{}`";
+    CLI_NUMBER_EXPECTED: "после `{}` ожидается число", "`{}` expects a number";
+    CLI_SECTION_NUMBER_EXPECTED: "после `{}` ожидается номер раздела", "`{}` expects a section number";
+    BAD_SECTION_NUMBER: "некорректный номер раздела: `{}`", "invalid section number: `{}`";
+
+    // Отметки результатов в `verify`, `--bless` и `check`: по ширине выровнены в колонку
+    STATUS_OK: "ok      {}", "ok      {}";
+    STATUS_ERROR: "ОШИБКА  {}", "ERROR   {}";
+    STATUS_MISMATCH: "РАЗНИЦА {}", "DIFF    {}";
+    STATUS_NO_ANNOTATIONS: "--      {} — нет аннотаций", "--      {} — no annotations";
+    STATUS_UPDATED: "обновлён {}", "updated {}";
+    STATUS_SOLVED: "решено  {}", "solved  {}";
+    STATUS_WRONG: "НЕВЕРНО {}", "WRONG   {}";
+
+    // Запуск разделов
+    RUN_TIME: "--- Время: {}", "--- Time: {}";
+    RUN_TOTAL: "Итого: выполнено {}, с паникой {}, пропущено {}", "Total: passed {}, panicked {}, skipped {}";
+    RUN_TOTAL_TIME: "Общее время разделов: {}", "Total section time: {}";
+    RUN_SKIPPED: "Раздел намеренно завершается паникой, запустите его с флагом --panics",
+        "The section panics on purpose, run it with --panics";
+    RUN_PANICKED: "!!! Раздел {} {} завершился паникой: {}", "!!! Section {} {} panicked: {}";
+    RUN_OWNERSHIP: "--- Владение:", "--- Ownership:";
+    RUN_MEMORY: "--- Память: выделений {}, освобождений {}, перевыделений {}, пик {} байт",
+        "--- Memory: allocations {}, deallocations {}, reallocations {}, peak {} bytes";
+    RUN_MEMORY_UNAVAILABLE: "--- Память: учёт недоступен, `alloc::Counting` не установлен",
+        "--- Memory: not tracked, `alloc::Counting` is not installed";
+    RUN_PANIC: "раздел завершился паникой", "the section panicked";
+    RUN_PANIC_NO_MESSAGE: "паника без сообщения", "panic without a message";
+    UNIT_NANOS: "нс", "ns";
+    UNIT_MICROS: "мкс", "µs";
+    UNIT_MILLIS: "мс", "ms";
+    UNIT_SECS: "с", "s";
+
+    // Хроника владения
+    TRACE_CREATED: "создано", "created";
+    TRACE_MOVED: "перемещено", "moved";
+    TRACE_MOVED_INTO: "{} в замыкание `{}`", "{} into closure `{}`";
+    TRACE_CLONED: "клон", "cloned";
+    TRACE_DROPPED: "удалено", "dropped";
+
+    // `start bench`
+    BENCH_DEBUG: "Внимание: отладочная сборка, для честных замеров используйте `cargo run --release -- bench`",
+        "Warning: debug build, use `cargo run --release -- bench` for fair measurements";
+    BENCH_SETUP: "Прогрев: {}, замеров: {}, вывод разделов отбрасывается",
+        "Warmup: {}, runs: {}, section output is discarded";
+    BENCH_RESULT: "{}  мин {}  медиана {}  макс {}", "{}  min {}  median {}  max {}";
+    BENCH_PANIC: "{}  паника: {}", "{}  panic: {}";
+    BENCH_IMPERATIVE: "императивный стиль", "imperative style";
+    BENCH_FUNCTIONAL: "функциональный стиль", "functional style";
+
+    // `start progress`
+    PROGRESS_UNREADABLE: "Предупреждение: {} не прочитан ({}), прогресс начнётся заново",
+        "Warning: {} could not be read ({}), progress starts over";
+    PROGRESS_NOT_SAVED: "Предупреждение: прогресс не сохранён в {}: {}", "Warning: progress was not saved to {}: {}";
+    PROGRESS_BAD_LINE: "строка {}: `{}`", "line {}: `{}`";
+    PROGRESS_SECTIONS: "разделов {}/{}", "sections {}/{}";
+    PROGRESS_PANICKED: ", с паникой {}", ", panicked {}";
+    PROGRESS_EXERCISES: ", заданий решено {}/{}", ", exercises solved {}/{}";
+    PROGRESS_LAST: ", последний раз {}", ", last run {}";
+    PROGRESS_CONTINUE: "Продолжить с раздела {} {}: start {}", "Continue with section {} {}: start {}";
+    PROGRESS_DONE: "Все разделы пройдены", "All sections passed";
+
+    // `start verify` и `start --bless`
+    VERIFY_TOTAL: "Итого: совпало {}, расхождений {}, без аннотаций {}, с ошибкой {}",
+        "Total: matched {}, mismatched {}, without annotations {}, failed {}";
+    VERIFY_SHOULD_PANIC: "раздел должен был завершиться паникой", "the section should have panicked";
+    VERIFY_EXPECTED_PANIC: "ok      {} — паника, как и ожидалось: {}", "ok      {} — panicked as expected: {}";
+    BLESS_SKIPPED: "--      {} — нет аннотаций, пропущен", "--      {} — no annotations, skipped";
+    BLESS_TOTAL: "Обновлено разделов: {}", "Sections updated: {}";
+    BLESS_REBUILD: "Пересоберите программу, чтобы `start verify` увидел новые аннотации",
+        "Rebuild the program so that `start verify` sees the new annotations";
+    BLESS_CHANGED: "файл изменился после сборки, пересоберите программу",
+        "the file has changed since the build, rebuild the program";
+
+    // `start check`
+    CHECK_TOTAL: "Итого: решено {} из {}", "Total: solved {} of {}";
+    EXERCISE_COMPLEX_DISPLAY: "Display для комплексного числа", "Display for a complex number";
+    EXERCISE_LIST_INDEX: "Вывод индексов в списке", "Printing indices in a list";
+
+    // `start quiz`
+    QUIZ_EMPTY: "В выбранных разделах нет вопросов", "The selected sections have no questions";
+    QUIZ_QUESTION: "Вопрос {}/{} — {} {} ({})", "Question {}/{} — {} {} ({})";
+    QUIZ_PROMPT: "Что напечатает строка, отмеченная ▶?", "What does the line marked ▶ print?";
+    QUIZ_PROMPT_CHOICES: "Что напечатает строка, отмеченная ▶? Введите номер варианта:",
+        "What does the line marked ▶ print? Enter the number of your choice:";
+    QUIZ_CORRECT: "Верно!", "Correct!";
+    QUIZ_WRONG: "Неверно, правильный ответ: {}", "Wrong, the correct answer is: {}";
+    QUIZ_TOTAL: "Итого: {} из {}", "Score: {} of {}";
+
+    // `start explain` и `start interactive`
+    EXPLAIN_NO_THEORY: "(теории к разделу нет)", "(this section has no theory)";
+    EXPLAIN_SOURCE: "--- Код {} ({}:{}) ---", "--- Code of {} ({}:{}) ---";
+    EXPLAIN_EXPECTED: "--- Ожидаемый вывод ---", "--- Expected output ---";
+    EXPLAIN_NO_ANNOTATIONS: "(аннотаций `// >>` нет)", "(no `// >>` annotations)";
+    INTERACTIVE_PROMPT: "[n] дальше, [p] назад, [r] повторить, [q] выход или номер раздела > ",
+        "[n] next, [p] previous, [r] repeat, [q] quit or a section number > ";
+    INTERACTIVE_LAST: "Это последний раздел", "This is the last section";
+    INTERACTIVE_FIRST: "Это первый раздел", "This is the first section";
+    INTERACTIVE_OUTPUT: "--- Вывод {} ---", "--- Output of {} ---";
+    INTERACTIVE_UNKNOWN: "неизвестная команда `{}`", "unknown command `{}`";
+    INTERACTIVE_NOT_FOUND: "раздел {} не найден", "section {} not found";
+
+    // `start search`
+    SEARCH_THEORY: "теория", "theory";
+    SEARCH_MORE: "… и ещё совпадений: {}", "… and {} more matches";
+    SEARCH_NOTHING: "Ничего не найдено по запросу «{}»", "Nothing found for “{}”";
+    SEARCH_FOUND: "Найдено разделов: {}. Запустить: start <номер>", "Sections found: {}. Run: start <number>";
+
+    // `start export`
+    EXPORT_UNKNOWN_FORMAT: "неизвестный формат `{}`, ожидается md или html", "unknown format `{}`, expected md or html";
+    EXPORT_WRITTEN: "записан {}", "written {}";
+    EXPORT_CONTENTS: "Оглавление", "Contents";
+    EXPORT_OUTPUT: "Вывод:", "Output:";
+    EXPORT_PANIC: "Раздел завершается паникой:", "The section panics:";
+
+    // `start serve`
+    SERVE_BIND: "не удалось открыть {}: {}", "cannot listen on {}: {}";
+    SERVE_LISTENING: "Книга доступна на http://{}/ (Ctrl+C — остановить)", "The book is served at http://{}/ (Ctrl+C to stop)";
+    SERVE_NOT_FOUND: "Страница {} не найдена", "Page {} not found";
+    SERVE_METHOD: "Метод {} не поддерживается", "Method {} is not supported";
+    SERVE_RUN: "Запустить", "Run";
+    SERVE_RUN_TIME: "Выполнено за {}", "Ran in {}";
+
+    // `start tui`
+    TUI_NO_TERMINAL: "`tui` работает только в терминале", "`tui` only works in a terminal";
+    TUI_STTY: "не удалось настроить терминал через stty: {}", "cannot set up the terminal with stty: {}";
+    TUI_THEORY: "Теория", "Theory";
+    TUI_SOURCE: "Код", "Code";
+    TUI_OUTPUT: "Вывод", "Output";
+    TUI_NO_FUNCTION: "(у раздела нет своей функции)", "(this section has no function of its own)";
+    TUI_SEARCH: "Поиск: {}", "Search: {}";
+    TUI_FOUND: "«{}»: найдено разделов {}; Esc — сбросить, / — новый поиск",
+        "\"{}\": {} sections found; Esc to clear, / for a new search";
+    TUI_HELP: "↑↓ раздел  ←→ свернуть/развернуть  Tab панель  PgUp/PgDn прокрутка  / поиск  r запуск  q выход",
+        "↑↓ section  ←→ fold/unfold  Tab pane  PgUp/PgDn scroll  / search  r run  q quit";
+
+    // `start edit`
+    CLI_ONE_SECTION: "`{}` ожидает номер одного раздела", "`{}` expects the number of a single section";
+    EDIT_NO_FUNCTION: "у раздела {} нет своей функции", "section {} has no function of its own";
+    EDIT_FILE: "Функция {} — в файле {}, строка {}", "The function {} is in {}, line {}";
+    EDIT_EDITOR_FAILED: "не удалось запустить редактор `{}`: {}", "failed to run the editor `{}`: {}";
+    EDIT_BUILDING: "Сборка изменённого кода…", "Building the edited code…";
+    EDIT_ORIGINAL: "--- Оригинал ---", "--- Original ---";
+    EDIT_EDITED: "--- Изменённый код ---", "--- Edited code ---";
+    EDIT_BUILD_FAILED: "Изменённый код не собрался", "The edited code does not compile";
+    EDIT_OUTDATED: "Глава в книге изменилась после ваших правок, копия собрана из старого кода",
+        "The chapter has changed in the book since your edits, the copy is based on the old code";
+    EDIT_RESET_HINT: "Начать заново с кода из книги: start edit {} --reset",
+        "To start over from the book's code: start edit {} --reset";
+    EDIT_SAME: "Вывод совпадает с оригиналом", "The output is the same as the original";
+    EDIT_DIFFERENT: "Вывод отличается от оригинала", "The output differs from the original";
+
+    // `start errors`
+    NO_COMPILE_ERRORS: "в выбранных разделах нет примеров ошибок компиляции",
+        "the selected sections have no compile error examples";
+    ERRORS_NOT_FOUND: "строка не найдена в функции {}", "the line is not in the function {}";
+    ERRORS_COMPILES: "компилируется без ошибок", "compiles without errors";
+    ERRORS_OTHER_CODE: "ожидалась ошибка {}, компилятор выдал: {}", "expected error {}, the compiler reported: {}";
+    ERRORS_TOTAL: "Итого: ошибка подтверждена для {} из {}", "Total: the error is confirmed for {} of {}";
+
+    // `start watch`
+    WATCH_NO_SOURCES: "в {} нет исходников", "there are no sources in {}";
+    WATCH_WAITING: "Жду изменений в {} (Ctrl+C — выход)…", "Waiting for changes in {} (Ctrl+C to quit)…";
+    WATCH_CHANGED: "=== Изменён {}, пересборка ===", "=== {} changed, rebuilding ===";
+    WATCH_BUILD_FAILED: "Сборка не удалась, исправьте ошибки и сохраните файл",
+        "The build failed, fix the errors and save the file";
+    WATCH_NO_CARGO: "Не удалось запустить cargo: {}", "Failed to run cargo: {}";
+    WATCH_NO_EXECUTABLE: "cargo не сообщил, где собранная программа",
+        "cargo did not report where the built program is";
+    WATCH_RUN_FAILED: "Не удалось запустить {}: {}", "Failed to run {}: {}";
+
+    // `start lang-check`
+    LANG_CHECK_EMPTY: "{}: нет перевода ({})", "{}: missing translation ({})";
+    LANG_CHECK_PLACEHOLDERS: "{}: в русском и английском тексте разное число подстановок",
+        "{}: the Russian and English texts have a different number of placeholders";
+    LANG_CHECK_NO_TITLE: "{} {}: нет английского заголовка", "{} {}: no English title";
+    LANG_CHECK_NO_THEORY: "{} {}: нет английской теории", "{} {}: no English theory";
+    LANG_CHECK_EXTRA_THEORY: "{} {}: есть английская теория, но нет русской",
+        "{} {}: has English theory but no Russian theory";
+    LANG_CHECK_UNKNOWN_SECTION: "раздела {} из каталога заголовков нет в книге",
+        "section {} from the title catalog is not in the book";
+    LANG_CHECK_NO_PATTERN: "{}:{}: нет перевода строки «{}»", "{}:{}: no translation for “{}”";
+    LANG_CHECK_PATTERN_HOLES: "«{}»: в переводе другое число подстановок",
+        "“{}”: the translation has a different number of placeholders";
+    LANG_CHECK_UNUSED_PATTERN: "{}: строки «{}» в исходнике нет", "{}: the string “{}” is not in the source";
+    LANG_CHECK_UNTRANSLATED: "{} {}: не переведён вывод «{}»", "{} {}: untranslated output “{}”";
+    LANG_CHECK_WARNING: "--      {}", "--      {}";
+    LANG_CHECK_TOTAL: "Итого: сообщений {}, разделов {}, строк вывода {}; ошибок {}, предупреждений {}",
+        "Total: messages {}, sections {}, output strings {}; errors {}, warnings {}";
+}
@@ -0,0 +1,142 @@
+// Язык вывода: русский (язык книги) или английский.
+// Выбирается флагом `--lang ru|en`, а без него — по переменным окружения
+// `LC_ALL`, `LC_MESSAGES` и `LANG`, как у остальных программ.
+//
+// Переводы хранятся в каталогах:
+// - `messages` — сообщения самой программы `start`, по идентификатору (`RUN_TOTAL`);
+// - `book` — заголовки и теория разделов, по номеру раздела;
+// - `output` — строки, которые печатают примеры. Как в gettext, ключом служит исходная
+//   русская строка форматирования, поэтому код примеров не меняется: вывод раздела
+//   переводится на лету по этим шаблонам.
+// `start lang-check` проверяет, что у каждой записи есть оба перевода и ничего не забыто.
+
+mod book;
+mod check;
+pub mod messages;
+mod output;
+
+use std::cell::Cell;
+use std::fmt::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+pub use book::{theory, title};
+pub use check::check;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Lang {
+    Ru,
+    En,
+}
+
+// Выбранный язык: `false` — русский, `true` — английский
+static ENGLISH: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    // Язык внутри `with_lang`: действует только в этом потоке,
+    // поэтому не меняет язык параллельно идущих тестов
+    static SCOPED: Cell<Option<Lang>> = const { Cell::new(None) };
+}
+
+impl Lang {
+    pub fn parse(name: &str) -> Result<Lang, String> {
+        match name {
+            "ru" => Ok(Lang::Ru),
+            "en" => Ok(Lang::En),
+            _ => Err(crate::tr!(LANG_UNKNOWN, name)),
+        }
+    }
+
+    // Код языка для `--lang` и `<html lang="...">`
+    pub fn code(self) -> &'static str {
+        match self {
+            Lang::Ru => "ru",
+            Lang::En => "en",
+        }
+    }
+
+    // `en_US.UTF-8` и подобные — английский, всё остальное, в том числе `C`, — русский
+    pub fn from_env() -> Lang {
+        let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty());
+        match locale {
+            Some(locale) if locale.starts_with("en") => Lang::En,
+            _ => Lang::Ru,
+        }
+    }
+}
+
+pub fn lang() -> Lang {
+    if let Some(lang) = SCOPED.get() {
+        return lang;
+    }
+    if ENGLISH.load(Ordering::Relaxed) {
+        Lang::En
+    } else {
+        Lang::Ru
+    }
+}
+
+pub fn set_lang(lang: Lang) {
+    ENGLISH.store(lang == Lang::En, Ordering::Relaxed);
+}
+
+// Выполняет `f` на языке `lang`. Нужно, например, чтобы сверять вывод с аннотациями `// >>`:
+// они записаны по-русски.
+pub fn with_lang<T>(lang: Lang, f: impl FnOnce() -> T) -> T {
+    let previous = SCOPED.replace(Some(lang));
+    let result = f();
+    SCOPED.set(previous);
+    result
+}
+
+// Строка вывода примеров на текущем языке, например ожидаемый вывод из аннотаций `// >>`
+pub fn output(text: &str) -> String {
+    match lang() {
+        Lang::Ru => text.to_string(),
+        Lang::En => output::translate(text),
+    }
+}
+
+// Сообщение программы на обоих языках. `{}` в тексте по порядку заменяются аргументами.
+pub struct Message {
+    pub id: &'static str,
+    pub ru: &'static str,
+    pub en: &'static str,
+}
+
+impl Message {
+    pub fn text(&self) -> &'static str {
+        match lang() {
+            Lang::Ru => self.ru,
+            Lang::En => self.en,
+        }
+    }
+
+    pub fn format(&self, args: &[&dyn fmt::Display]) -> String {
+        let mut text = String::new();
+        let mut args = args.iter();
+        let mut parts = self.text().split("{}");
+        text.push_str(parts.next().unwrap_or_default());
+        for part in parts {
+            if let Some(arg) = args.next() {
+                write!(text, "{}", arg).unwrap();
+            }
+            text.push_str(part);
+        }
+        text
+    }
+}
+
+// Текст сообщения из каталога `messages` на текущем языке:
+// `tr!(RUN_TOTAL, passed, panicked, skipped)`
+#[macro_export]
+macro_rules! tr {
+    ($id:ident) => {
+        $crate::i18n::messages::$id.text()
+    };
+    ($id:ident, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::messages::$id.format(&[$(&$arg),+])
+    };
+}
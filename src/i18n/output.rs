@@ -0,0 +1,267 @@
+// Перевод вывода примеров по каталогу `output.txt`.
+// Примеры печатают текст по-русски; `Output` в английском режиме после раздела пропускает
+// текст каждого вызова `write!` через `translate`: текст сопоставляется с русскими строками форматирования,
+// найденные подстановки переводятся отдельно и вставляются в английскую строку.
+
+use std::sync::OnceLock;
+
+// Запись каталога: русская строка форматирования и её перевод
+pub struct Phrase {
+    // Файл, из которого взята строка, или `values` для строковых данных примеров
+    pub group: &'static str,
+    pub ru: &'static str,
+    pub en: &'static str,
+    pattern: Vec<Piece>,
+}
+
+enum Piece {
+    Text(String),
+    Hole,
+}
+
+impl Phrase {
+    pub fn holes(&self) -> usize {
+        count_holes(&self.pattern)
+    }
+
+    // Сколько в шаблоне постоянного текста: из нескольких подходящих шаблонов
+    // выбирается самый конкретный (`Соответствует {:?}!` точнее `Соответствует {:?}`)
+    fn weight(&self) -> usize {
+        self.pattern
+            .iter()
+            .map(|piece| match piece {
+                Piece::Text(text) => text.chars().count(),
+                Piece::Hole => 0,
+            })
+            .sum()
+    }
+
+    fn fill(&self, values: Vec<String>) -> String {
+        let mut values = values.into_iter();
+        let mut text = String::new();
+        for piece in parse(self.en) {
+            match piece {
+                Piece::Text(part) => text.push_str(&part),
+                Piece::Hole => text.push_str(&values.next().unwrap_or_default()),
+            }
+        }
+        text
+    }
+}
+
+pub fn phrases() -> &'static [Phrase] {
+    static PHRASES: OnceLock<Vec<Phrase>> = OnceLock::new();
+    PHRASES.get_or_init(|| load(include_str!("output.txt")))
+}
+
+// Записи вида `ru: ...` и следующая за ней `en: ...`, сгруппированные строками `@ <файл>`
+fn load(text: &'static str) -> Vec<Phrase> {
+    let mut phrases = Vec::new();
+    let mut group = None;
+    let mut ru = None;
+    for line in text.lines() {
+        if let Some(name) = line.strip_prefix("@ ") {
+            group = Some(name.trim());
+        } else if let Some(text) = line.strip_prefix("ru: ") {
+            ru = Some(text);
+        } else if let (Some(group), Some(en)) = (group, line.strip_prefix("en: ")) {
+            let ru = ru
+                .take()
+                .expect("в output.txt перед `en:` должна идти строка `ru:`");
+            phrases.push(Phrase {
+                group,
+                ru,
+                en,
+                pattern: parse(ru),
+            });
+        }
+    }
+    phrases
+}
+
+// Строка форматирования по частям: `{}`, `{0:?}`, `{big}` — подстановки, `{{` и `}}` — скобки
+fn parse(format: &str) -> Vec<Piece> {
+    let mut pieces = Vec::new();
+    let mut text = String::new();
+    let mut chars = format.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                text.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                text.push('}');
+            }
+            '{' => {
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        break;
+                    }
+                }
+                if !text.is_empty() {
+                    pieces.push(Piece::Text(std::mem::take(&mut text)));
+                }
+                pieces.push(Piece::Hole);
+            }
+            c => text.push(c),
+        }
+    }
+    if !text.is_empty() {
+        pieces.push(Piece::Text(text));
+    }
+    pieces
+}
+
+fn count_holes(pattern: &[Piece]) -> usize {
+    pattern
+        .iter()
+        .filter(|piece| matches!(piece, Piece::Hole))
+        .count()
+}
+
+// Число подстановок в строке форматирования
+pub fn holes(format: &str) -> usize {
+    count_holes(&parse(format))
+}
+
+pub fn has_cyrillic(text: &str) -> bool {
+    text.chars().any(|c| matches!(c, 'А'..='я' | 'Ё' | 'ё'))
+}
+
+// Перевод вывода одного вызова `write!`. Завершающий перевод строки от `writeln!`
+// в строку форматирования не входит и сохраняется как есть.
+pub fn translate(text: &str) -> String {
+    match text.strip_suffix('\n') {
+        Some(line) => format!("{}\n", translate_text(line)),
+        None => translate_text(text),
+    }
+}
+
+fn translate_text(text: &str) -> String {
+    if !has_cyrillic(text) {
+        return text.to_string();
+    }
+    let mut best: Option<(&Phrase, Vec<&str>)> = None;
+    for phrase in phrases() {
+        if best
+            .as_ref()
+            .is_some_and(|(found, _)| found.weight() >= phrase.weight())
+        {
+            continue;
+        }
+        let mut values = Vec::new();
+        if capture(&phrase.pattern, text, &mut values) {
+            best = Some((phrase, values));
+        }
+    }
+    match best {
+        Some((phrase, values)) => phrase.fill(values.into_iter().map(translate_text).collect()),
+        None => translate_values(text),
+    }
+}
+
+// Сопоставляет текст с шаблоном; подстановки забирают как можно меньше текста
+fn capture<'t>(pattern: &[Piece], text: &'t str, values: &mut Vec<&'t str>) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some((Piece::Text(part), rest)) => text
+            .strip_prefix(part.as_str())
+            .is_some_and(|text| capture(rest, text, values)),
+        Some((Piece::Hole, [])) => {
+            values.push(text);
+            true
+        }
+        Some((Piece::Hole, rest)) => {
+            let ends = text.char_indices().map(|(i, _)| i).chain([text.len()]);
+            for end in ends {
+                values.push(&text[..end]);
+                if capture(rest, &text[end..], values) {
+                    return true;
+                }
+                values.pop();
+            }
+            false
+        }
+    }
+}
+
+// Текст, для которого нет шаблона, например `{:?}` вектора строк:
+// переводятся строки в кавычках и известные значения из группы `values`
+fn translate_values(text: &str) -> String {
+    let mut translated = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('"') {
+        let Some(len) = rest[start + 1..].find('"') else {
+            break;
+        };
+        let quoted = &rest[start + 1..start + 1 + len];
+        translated.push_str(&replace_values(&rest[..start]));
+        translated.push('"');
+        translated.push_str(&translate_text(quoted));
+        translated.push('"');
+        rest = &rest[start + len + 2..];
+    }
+    translated.push_str(&replace_values(rest));
+    translated
+}
+
+fn replace_values(text: &str) -> String {
+    let mut text = text.to_string();
+    let mut values: Vec<&Phrase> = phrases()
+        .iter()
+        .filter(|phrase| phrase.group == "values")
+        .collect();
+    // Длинные значения раньше коротких, чтобы не заменить часть более длинного
+    values.sort_by_key(|phrase| std::cmp::Reverse(phrase.ru.len()));
+    for value in values {
+        if text.contains(value.ru) {
+            text = text.replace(value.ru, value.en);
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_the_trailing_newline() {
+        assert_eq!(translate("Привет, мир!\n"), "Hello, World!\n");
+        assert_eq!(translate("Привет, мир!"), "Hello, World!");
+    }
+
+    // Подставленные значения тоже переводятся (`Aлиса` в примерах — с латинской `A`)
+    #[test]
+    fn fills_holes_with_captured_values() {
+        assert_eq!(translate("31 дней\n"), "31 days\n");
+        assert_eq!(
+            translate("Aлиса, это Боб. Боб, это Aлиса\n"),
+            "Alice, this is Bob. Bob, this is Alice\n"
+        );
+    }
+
+    // Из подходящих шаблонов выбирается тот, в котором больше постоянного текста
+    #[test]
+    fn prefers_the_most_specific_pattern() {
+        assert_eq!(translate("Соответствует 5!"), "Matched 5!");
+        assert_eq!(translate("Соответствует 5"), "Matched 5");
+    }
+
+    #[test]
+    fn translates_quoted_values_without_a_pattern() {
+        assert_eq!(
+            translate("[\"Aлиса\", \"Боб\"]\n"),
+            "[\"Alice\", \"Bob\"]\n"
+        );
+    }
+
+    #[test]
+    fn leaves_text_without_cyrillic_unchanged() {
+        assert_eq!(translate("x = 5\n"), "x = 5\n");
+        assert_eq!(translate("[1, 2, 3]"), "[1, 2, 3]");
+        assert_eq!(translate(""), "");
+    }
+}
@@ -0,0 +1,99 @@
+// `start interactive`: пошаговое прохождение книги.
+// Для каждого раздела показываются заголовок, теория и вывод, затем программа ждёт команду.
+
+use std::io::{self, BufRead, Write};
+
+use crate::book::{Section, SectionNumber};
+use crate::explain;
+use crate::progress::Progress;
+use crate::runner;
+use crate::tr;
+
+pub fn interactive(sections: &[&Section], progress: &mut Progress) {
+    // Разделы, которые намеренно паникуют, здесь тоже запускаются: паника будет показана как результат
+    let options = runner::Options {
+        panics: true,
+        ..runner::Options::default()
+    };
+    let mut input = io::stdin().lock();
+    let mut current = 0;
+
+    // Закрытый вывод — конец сеанса, как и `q`
+    if show(sections[current], &options, progress).is_err() {
+        return;
+    }
+    loop {
+        print!("{}", tr!(INTERACTIVE_PROMPT));
+        io::stdout().flush().unwrap();
+
+        let mut line = String::new();
+        // Конец ввода (Ctrl+D) — то же, что `q`
+        if input.read_line(&mut line).unwrap_or(0) == 0 {
+            println!();
+            return;
+        }
+
+        match line.trim() {
+            "" | "n" => {
+                if current + 1 == sections.len() {
+                    println!("{}", tr!(INTERACTIVE_LAST));
+                    continue;
+                }
+                current += 1;
+            }
+            "p" => {
+                if current == 0 {
+                    println!("{}", tr!(INTERACTIVE_FIRST));
+                    continue;
+                }
+                current -= 1;
+            }
+            "r" => {}
+            "q" => return,
+            command => match find(sections, command) {
+                Ok(index) => current = index,
+                Err(err) => {
+                    println!("{}", err);
+                    continue;
+                }
+            },
+        }
+        if show(sections[current], &options, progress).is_err() {
+            return;
+        }
+    }
+}
+
+fn show(section: &Section, options: &runner::Options, progress: &mut Progress) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+    writeln!(stdout)?;
+    writeln!(stdout, "=== {} {} ===", section.number, section.title())?;
+
+    let theory = section.theory();
+    if !theory.is_empty() {
+        writeln!(stdout)?;
+        writeln!(
+            stdout,
+            "{}",
+            explain::wrap(&theory, explain::terminal_width())
+        )?;
+    }
+    if section.run.is_some() {
+        writeln!(stdout)?;
+        writeln!(stdout, "{}", tr!(INTERACTIVE_OUTPUT, section.func()))?;
+        if let Some((status, _)) = runner::run_section(section, &mut stdout, options)? {
+            status.record(section, progress);
+            // Сохраняем сразу: интерактивный режим обычно закрывают посреди книги
+            progress.save();
+        }
+    }
+    writeln!(stdout)
+}
+
+fn find(sections: &[&Section], command: &str) -> Result<usize, String> {
+    let number = SectionNumber::parse(command).map_err(|_| tr!(INTERACTIVE_UNKNOWN, command))?;
+    sections
+        .iter()
+        .position(|section| section.number() == number)
+        .ok_or_else(|| tr!(INTERACTIVE_NOT_FOUND, number))
+}
@@ -0,0 +1,71 @@
+//! Учебник по Rust в виде программы: каждая глава — модуль с разделами,
+//! а раздел — функция, которая печатает свои примеры в [`output::Output`].
+//!
+//! Реестр всех разделов возвращает [`book::sections`], типы из примеров
+//! (например, [`primitives::Matrix`] или [`conversion::EvenNumber`]) можно
+//! использовать в других инструментах и интеграционных тестах.
+
+// Инфраструктура: реестр разделов, запуск, проверка и прочие режимы `start`
+pub mod alloc;
+pub mod annotations;
+pub mod bench;
+pub mod bless;
+#[macro_use]
+#[warn(missing_docs)]
+pub mod book;
+pub mod check;
+pub mod edit;
+pub mod errors;
+pub mod exercises;
+pub mod explain;
+pub mod export;
+pub mod i18n;
+pub mod interactive;
+#[warn(missing_docs)]
+pub mod output;
+pub mod progress;
+pub mod quiz;
+pub mod report;
+pub mod runner;
+pub mod scratch;
+pub mod search;
+pub mod serve;
+pub mod theory;
+pub mod toc;
+pub mod trace;
+pub mod tui;
+pub mod verify;
+pub mod watch;
+
+// Главы книги, каждая включается своей возможностью `ch-*` из Cargo.toml.
+// Их публичные типы и разделы документированы.
+#[cfg(feature = "ch-conversion")]
+#[warn(missing_docs)]
+pub mod conversion;
+#[cfg(feature = "ch-expressions")]
+#[warn(missing_docs)]
+pub mod expressions;
+#[cfg(feature = "ch-functions")]
+#[warn(missing_docs)]
+pub mod func;
+#[cfg(feature = "ch-formatting")]
+#[warn(missing_docs)]
+pub mod getting_started;
+#[cfg(feature = "ch-flow-control")]
+#[warn(missing_docs)]
+pub mod loop_control;
+#[cfg(feature = "ch-modules")]
+#[warn(missing_docs)]
+pub mod mods;
+#[cfg(feature = "ch-primitives")]
+#[warn(missing_docs)]
+pub mod primitives;
+#[cfg(feature = "ch-types")]
+#[warn(missing_docs)]
+pub mod types;
+#[cfg(feature = "ch-custom-types")]
+#[warn(missing_docs)]
+pub mod use_type;
+#[cfg(feature = "ch-variable-bindings")]
+#[warn(missing_docs)]
+pub mod variable_binding;
@@ -1,7 +1,11 @@
+//! Глава 8 «Управление потоком»: `if`/`else`, циклы, `match`, `if let` и `while let`.
+
 // Атрибут, который убирает предупреждения компилятора о неиспользуемом коде
-#![allow(dead_code)]
 #![allow(unused_variables)]
 
+use crate::book::Section;
+use crate::output::Output;
+
 // 8 Управление потоком
 // Неотъемлемой частью любого языка программирования являются управляющие конструкции: if / else , for и другие.
 
@@ -14,28 +18,31 @@
 Условные операторы if-else являются выражениями, и все ветки должны возвращать значения одного и того же типа.
 */
 
-fn if_else() {
+/// Ветвление `if`/`else` как выражение, возвращающее значение.
+pub fn if_else(out: &Output) {
     let n = 5;
     if n < 0 {
-        println!("{} - Отрицательное число", n);
+        writeln!(out, "{} - Отрицательное число", n);
     } else if n > 0 {
-        println!("{} - Положительное число", n);
+        writeln!(out, "{} - Положительное число", n);
     } else {
-        println!("{} - нуль", n);
+        writeln!(out, "{} - нуль", n);
     }
+    // >> 5 - Положительное число
 
     let big_n = if n < 10 && n > -10 {
-        println!(", малое по модулю число, умножим его в десять раз");
+        writeln!(out, ", малое по модулю число, умножим его в десять раз");
         // Это выражение вернёт `i32`.
         10 * n
     } else {
-        println!(", большое по модулю число, уменьшим его в вдвое");
+        writeln!(out, ", большое по модулю число, уменьшим его в вдвое");
         // И это выражение вернёт `i32`.
         n / 2
     };
+    // >> , малое по модулю число, умножим его в десять раз
     // Не забудьте добавить тут точку с запятой! Все операторы `let` требуют её..
 
-    println!("{} -> {:?}", n, big_n);
+    writeln!(out, "{} -> {:?}", n, big_n);
     // >> 5 -> 50
 }
 
@@ -47,28 +54,38 @@ Rust предоставляет ключевое слово loop для обоз
     чтобы пропустить оставшуюся часть цикла и начать новую итерацию.
 */
 
-fn loop_() {
+/// Бесконечный цикл `loop` с `continue` и `break`.
+pub fn loop_(out: &Output) {
     let mut count = 0u32;
 
-    println!("Давайте считать до бесконечности!");
+    writeln!(out, "Давайте считать до бесконечности!");
 
     loop {
         count += 1;
 
         if count == 3 {
-            println!("Три");
+            writeln!(out, "Три");
             // Пропустить оставшуюся часть итерации
             continue;
         }
 
-        println!("{}", count);
+        writeln!(out, "{}", count);
 
         if count == 5 {
-            println!("Всё, достаточно");
+            writeln!(out, "Всё, достаточно");
             // Выйти из цикла
             break;
         }
     }
+    /*
+    >> Давайте считать до бесконечности!
+    >> 1
+    >> 2
+    >> Три
+    >> 4
+    >> 5
+    >> Всё, достаточно
+    */
 }
 
 // 8.2.1 Вложенность и метки
@@ -77,22 +94,28 @@ fn loop_() {
     когда речь заходит о вложенных циклах. Для этого циклы должны быть обозначены метками вроде 'label,
     а метки должны быть переданы операторам break или continue.
 */
+/// Вложенные циклы и выход из внешнего по метке.
 #[allow(unreachable_code)]
 #[allow(unused_labels)]
-
-fn nesting_and_labels() {
+#[allow(clippy::never_loop)]
+pub fn nesting_and_labels(out: &Output) {
     // Установка метки на внешний цикл
     'outer: loop {
-        println!("Вошли во внешний цикл");
+        writeln!(out, "Вошли во внешний цикл");
         // Установка метки на внутрений цикл
         'inner: loop {
-            println!("Вошли во внутрений цикл");
+            writeln!(out, "Вошли во внутрений цикл");
             // Завершения внешнего цикла внутри внутреного цикла. С помощью метки
             break 'outer;
         }
-        println!("Эта точка не будет достигнута");
+        writeln!(out, "Эта точка не будет достигнута");
     }
-    println!("Вышли из внешнего цикла");
+    writeln!(out, "Вышли из внешнего цикла");
+    /*
+    >> Вошли во внешний цикл
+    >> Вошли во внутрений цикл
+    >> Вышли из внешнего цикла
+    */
 }
 
 // 8.2.2 Возврат из циклов
@@ -102,7 +125,8 @@ fn nesting_and_labels() {
     поместите его после break, и оно будет возвращено выражением loop.
 */
 
-fn return_loop() {
+/// Возврат значения из `loop` через `break`.
+pub fn return_loop(out: &Output) {
     let mut counter = 0;
 
     let resul = loop {
@@ -113,7 +137,7 @@ fn return_loop() {
             break counter * 2;
         }
     };
-    println!("Результат: {} и счётчик: {}", resul, counter);
+    writeln!(out, "Результат: {} и счётчик: {}", resul, counter);
     // >> Результат: 20 и счётчик: 10
     assert_eq!(resul, 20);
 }
@@ -121,23 +145,35 @@ fn return_loop() {
 // 8.3 while
 // Ключевое слово while используется для создания цикла, который будет выполняться, пока условие истинно.
 
-fn while_() {
+/// FizzBuzz на цикле `while`.
+pub fn while_(out: &Output) {
     let mut n = 1;
 
     // Цикл while будет работать, пока `n` меньше 101
     while n < 101 {
         if n % 15 == 0 {
-            println!("fizzbuzz");
+            writeln!(out, "fizzbuzz");
         } else if n % 3 == 0 {
-            println!("fizz");
+            writeln!(out, "fizz");
         } else if n % 5 == 0 {
-            println!("buzz");
+            writeln!(out, "buzz");
         } else {
-            println!("{}", n);
+            writeln!(out, "{}", n);
         }
         // Увеличиваем значение счётчика
         n += 1;
     }
+    /*
+    >> 1
+    >> 2
+    >> fizz
+    >> 4
+    >> buzz
+    >> ...
+    >> 98
+    >> fizz
+    >> buzz
+    */
 }
 
 // 8.4 Цикл for и range
@@ -147,7 +183,8 @@ fn while_() {
 Это вернёт нам значения от a (включительно) до b (исключительно) за один шаг.
 */
 
-fn for_() {
+/// FizzBuzz на `for` по диапазону и обход коллекции через `iter`, `into_iter` и `iter_mut`.
+pub fn for_(out: &Output) {
     /*
     1.. 101 - `n` будет принимать значения: 1, 2, ..., 100 с каждой итерации
     Также, может быть использован диапазон a..=b, включающий оба конца. Код выше может быть записан следующим образом:
@@ -156,15 +193,24 @@ fn for_() {
 
     for n in 1..101 {
         if n % 15 == 0 {
-            println!("fizzbuzz");
+            writeln!(out, "fizzbuzz");
         } else if n % 3 == 0 {
-            println!("fizz");
+            writeln!(out, "fizz");
         } else if n % 5 == 0 {
-            println!("buzz");
+            writeln!(out, "buzz");
         } else {
-            println!("{}", n);
+            writeln!(out, "{}", n);
         }
     }
+    /*
+    >> 1
+    >> 2
+    >> fizz
+    >> ...
+    >> fizz
+    >> buzz
+    */
+
     /*
     Конструкция for in может взаимодействовать с итератором разными способами.
     Как обсуждается далее про типаж Iterator, цикл for применяет к предоставленной коллекции метод into_iter,
@@ -184,10 +230,15 @@ fn for_() {
     for name in names.iter() {
         match name {
             // Получение ссылки от строки "Ferris"
-            &"Ferris" => println!("Программисты Rust вокруг нас!"),
-            _ => println!("Привет {}", name),
+            &"Ferris" => writeln!(out, "Программисты Rust вокруг нас!"),
+            _ => writeln!(out, "Привет {}", name),
         }
     }
+    /*
+    >> Привет Bob
+    >> Привет Frank
+    >> Программисты Rust вокруг нас!
+    */
 
     /*
     into_iter - эта функция потребляет коллекцию так что на каждой итерации предоставляются данные.
@@ -197,10 +248,16 @@ fn for_() {
     for name in names.into_iter() {
         match name {
             // Тут не требуется ссылка так как into_iter получило владение над колеекцией `names`
-            "Ferris" => println!("Программисты Rust вокруг нас!"),
-            _ => println!("Привет {}", name),
+            "Ferris" => writeln!(out, "Программисты Rust вокруг нас!"),
+            _ => writeln!(out, "Привет {}", name),
         }
     }
+    /*
+    >> Привет Bob
+    >> Привет Frank
+    >> Программисты Rust вокруг нас!
+    */
+
     /*
     iter_mut - эта функция делает изменяемое заимствование каждого элемента коллекции, позволяя изменять коллекцию на месте.
     */
@@ -213,11 +270,11 @@ fn for_() {
             _ => "Привет",
         }
     }
-    println!("Изменёные имена {:?}", names);
+    writeln!(out, "Изменёные имена {:?}", names);
     // >> Изменёные имена ["Привет", "Привет", "Программисты Rust вокруг нас!"]
 }
 
-// 8.5 March
+// 8.5 Match
 
 /*
 Rust обеспечивает сопоставление с образцом с помощью ключевого слова match ,
@@ -225,21 +282,24 @@ Rust обеспечивает сопоставление с образцом с
 Срабатывает первая подходящая ветка, и все возможные значения должны быть перечислены.
 */
 
-fn _mathc() {
+/// Сопоставление числа с отдельными значениями, списками и диапазонами.
+pub fn _mathc(out: &Output) {
     let number = 13;
 
-    println!("Расскажи мне о {}", number);
+    writeln!(out, "Расскажи мне о {}", number);
+    // >> Расскажи мне о 13
 
     match number {
         // Одно значение
-        1 => println!("Один!"),
+        1 => writeln!(out, "Один!"),
         // Много значений
-        2 | 3 | 5 | 7 | 11 => println!("Это простое число"),
+        2 | 3 | 5 | 7 | 11 => writeln!(out, "Это простое число"),
         // Выбирает диапазон, включающий границы
-        13..=19 => println!("от 13 до 19"),
+        13..=19 => writeln!(out, "от 13 до 19"),
         // Обрабатывает оставшиеся случаи
-        _ => println!("Ничего особенного"),
+        _ => writeln!(out, "Ничего особенного"),
     }
+    // >> от 13 до 19
 
     let boolean = true;
     // Match ещё и выражение
@@ -247,7 +307,7 @@ fn _mathc() {
         false => 0,
         true => 1,
     };
-    println!("{} -> {}", boolean, binary);
+    writeln!(out, "{} -> {}", boolean, binary);
     // >> true -> 1
 }
 
@@ -263,68 +323,89 @@ fn _mathc() {
 */
 // 8.5.1.1 Кортежи
 
-fn match_tuple() {
+/// Деструктуризация кортежа в `match`.
+pub fn match_tuple(out: &Output) {
     let triple = (0, -2, 3);
 
-    println!("Расскажи мне о {:?}", triple);
+    writeln!(out, "Расскажи мне о {:?}", triple);
     // >> Расскажи мне о (0, -2, 3)
     // Для деструктурирования можно использовать match
     match triple {
         // Деструктурируем второй и третий элементы
-        (0, y, z) => println!("Первый равен `0`, `y` равен {:?}, `z` равен {:?}", y, z),
+        (0, y, z) => writeln!(
+            out,
+            "Первый равен `0`, `y` равен {:?}, `z` равен {:?}",
+            y, z
+        ),
         // `..` можно использовать, чтобы игнорировать оставшуюся часть кортежа
-        (1, ..) => println!("Первый равен `1`, остальное не важно"),
-        (.., 2) => println!("Последний равен `2`, остальное не важно"),
-        (3, .., 4) => println!("Первый равен `3`, последний равен `4`, остальное не важно"),
+        (1, ..) => writeln!(out, "Первый равен `1`, остальное не важно"),
+        (.., 2) => writeln!(out, "Последний равен `2`, остальное не важно"),
+        (3, .., 4) => writeln!(
+            out,
+            "Первый равен `3`, последний равен `4`, остальное не важно"
+        ),
         // `_` означает, значение не будет присвоено переменной
-        _ => println!("Не важно чему они равны"),
+        _ => writeln!(out, "Не важно чему они равны"),
     }
+    // >> Первый равен `0`, `y` равен -2, `z` равен 3
 }
 
 // 8.5.1.2 Перечисления
-enum Color {
+/// Цвет в одной из цветовых моделей.
+pub enum Color {
+    /// Красный.
     Red,
+    /// Синий.
     Blue,
+    /// Зелёный.
     Green,
+    /// Красная, зелёная и синяя компоненты.
     RGB(u32, u32, u32),
+    /// Тон, насыщенность и значение.
     HSV(u32, u32, u32),
+    /// Тон, насыщенность и светлота.
     HSL(u32, u32, u32),
+    /// Голубая, пурпурная и жёлтая компоненты.
     CMY(u32, u32, u32),
+    /// Голубая, пурпурная, жёлтая и чёрная компоненты.
     CMYK(u32, u32, u32, u32),
 }
 
-fn match_enum() {
+/// Деструктуризация вариантов перечисления в `match`.
+pub fn match_enum(out: &Output) {
     let color = Color::Blue;
     // let color = Color::RGB(122, 17, 40);
     // let color = Color::CMYK(122, 17, 12, 111);
 
-    println!("Какой это цвет?");
+    writeln!(out, "Какой это цвет?");
     // >> Какой это цвет?
 
     // `enum` может быть деструктурирован с помощью `match`.
     match color {
-        Color::Red => println!("Красный цвет!"),
-        Color::Blue => println!("Синий цвет!"),
-        Color::Green => println!("Зелёный цвет!"),
+        Color::Red => writeln!(out, "Красный цвет!"),
+        Color::Blue => writeln!(out, "Синий цвет!"),
+        Color::Green => writeln!(out, "Зелёный цвет!"),
         Color::RGB(r, g, b) => {
-            println!("Красный: {}, Зелёный: {}, Синий {}", r, g, b)
+            writeln!(out, "Красный: {}, Зелёный: {}, Синий {}", r, g, b)
         }
         Color::HSV(h, s, v) => {
-            println!("Тон: {}, Насыщеность: {}, Значение: {}", h, s, v)
+            writeln!(out, "Тон: {}, Насыщеность: {}, Значение: {}", h, s, v)
         }
         Color::HSL(h, s, l) => {
-            println!("Тон: {}, Насыщеность: {}, Светолота: {}", h, s, l)
+            writeln!(out, "Тон: {}, Насыщеность: {}, Светолота: {}", h, s, l)
         }
         Color::CMY(c, m, y) => {
-            println!("Голубой: {}, Пурпурный: {}, Жёлтый: {}", c, m, y)
+            writeln!(out, "Голубой: {}, Пурпурный: {}, Жёлтый: {}", c, m, y)
         }
         Color::CMYK(c, m, y, k) => {
-            println!(
+            writeln!(
+                out,
                 "Голубой: {}, Пурпурный: {}, Жёлтый: {}, key(чёрный) {}!",
                 c, m, y, k
             )
         }
     }
+    // >> Синий цвет!
 }
 
 // 8.5.1.3 Указатели и ссылки
@@ -337,7 +418,8 @@ fn match_enum() {
 
 */
 
-fn match_link_and_signposts() {
+/// Ссылки в шаблонах: `&`, `ref` и `ref mut`.
+pub fn match_link_and_signposts(out: &Output) {
     // Присваиваем ссылку на тип `i32`.
     // Символ `&` означает, что присваивается ссылка.
     let reference = &4;
@@ -349,15 +431,16 @@ fn match_link_and_signposts() {
         `&i32` = `&val`
         Мы видим, что если отбросить сопоставляемые `&`, то переменной `val` должно быть присвоено `i32`.
         */
-        &val => println!("Получаем значене через деструктуризацию: {:?}", val),
+        &val => writeln!(out, "Получаем значене через деструктуризацию: {:?}", val),
     }
     // >> Получаем значене через деструктуризацию: 4
 
     // Разыменование
     // Чтобы избежать символа `&`, нужно разыменовывать ссылку до сопоставления.
     // Разыменование `*`
+    #[allow(clippy::match_single_binding)]
     match *reference {
-        val => println!("Получаем значение через разменованвание: {}", val),
+        val => writeln!(out, "Получаем значение через разменованвание: {}", val),
     }
     // >> Получаем значение через разменованвание: 4
 
@@ -373,6 +456,7 @@ fn match_link_and_signposts() {
     Оно изменяет присваивание так, что создаётся ссылка для элемента.
     Теперь ссылка присвоена.
     */
+    #[allow(clippy::toplevel_ref_arg)]
     let ref _is_a_reference = 3;
 
     // Соответственно, для определения двух значений без ссылок,
@@ -381,46 +465,50 @@ fn match_link_and_signposts() {
     let mut mut_value = 6;
 
     // Используйте ключевое слово `ref` для создания ссылки.
+    #[allow(clippy::match_single_binding)]
     match value {
-        ref r => println!("Получим ссылку на значение: {:?}", r),
+        ref r => writeln!(out, "Получим ссылку на значение: {:?}", r),
     }
     // >> Получим ссылку на значение: 5
 
     // Используйте `ref mut` аналогичным образом.
+    #[allow(clippy::match_single_binding)]
     match mut_value {
         ref mut m => {
             // Получаем ссылку. Её нужно разыменовать, прежде чем мы сможем что-то добавить.
             *m += 10;
-            println!("Мы добавили 10. `mut_value`: {:?}", m);
+            writeln!(out, "Мы добавили 10. `mut_value`: {:?}", m);
         }
     }
-    // Мы добавили 10. `mut_value`: 16
+    // >> Мы добавили 10. `mut_value`: 16
 }
 
 // 8.5.1.4 Структуры
 
-fn match_struct() {
+/// Деструктуризация структуры, в том числе с `..`.
+pub fn match_struct(out: &Output) {
     struct Foo {
         x: (u32, u32),
         y: u32,
     }
 
     // деструктуризация члена структуры
+    #[allow(clippy::disallowed_names)]
     let foo = Foo { x: (1, 2), y: 3 };
     let Foo { x: (a, b), y } = foo;
 
-    println!("a={}, b={}, y={}", a, b, y);
+    writeln!(out, "a={}, b={}, y={}", a, b, y);
     // >> a=1, b=2, y=3
 
     // Вы можете деструктурировать структуру и переименовывать переменные,
     // порядок при этом не важен
     let Foo { y: i, x: j } = foo;
-    println!("i={:?}, j={:?}", i, j);
+    writeln!(out, "i={:?}, j={:?}", i, j);
     // >> i=3, j=(1, 2)
 
     // а так же можно проигнорировать часть переменных:
     let Foo { y, .. } = foo;
-    println!("y={}", y);
+    writeln!(out, "y={}", y);
     // >> y=3
 
     // следующий код выдаст ошибку: в шаблоне нет упоминания поля `x`
@@ -432,18 +520,20 @@ fn match_struct() {
 Внутри конструкции match можно добавить ограничитель шаблонов для фильтрации возможных вариантов.
 */
 
-fn match_limited() {
+/// Ограничители `if` в ветвях `match`.
+pub fn match_limited(out: &Output) {
     let pair = (2, -2);
-    println!("Pасскажи мне о {:?}", pair);
+    writeln!(out, "Pасскажи мне о {:?}", pair);
     // >> Pасскажи мне о (2, -2)
 
     match pair {
         // Данное ^ `условие if` является ограничителем шаблонов
-        (x, y) if x == y => println!("Близнецы"),
-        (x, y) if x + y == 0 => println!("Антиматерия, бабах!"),
-        (x, _) if x % 2 == 1 => println!("Первое число нечётное"),
-        _ => println!("Нет корреляции..."),
+        (x, y) if x == y => writeln!(out, "Близнецы"),
+        (x, y) if x + y == 0 => writeln!(out, "Антиматерия, бабах!"),
+        (x, _) if x % 2 == 1 => writeln!(out, "Первое число нечётное"),
+        _ => writeln!(out, "Нет корреляции..."),
     }
+    // >> Антиматерия, бабах!
 }
 // 8.5.3 Связывание
 
@@ -462,43 +552,50 @@ fn some_number() -> Option<u32> {
     Some(42)
 }
 
-fn match_tiung() {
-    println!("Скажи мне свой возраст");
+/// Связывание значения с именем через `@` в шаблоне.
+pub fn match_tiung(out: &Output) {
+    writeln!(out, "Скажи мне свой возраст");
 
     match age() {
-        0 => println!("Я ещё не отпраздновал свой первый день рождения"),
+        0 => writeln!(out, "Я ещё не отпраздновал свой первый день рождения"),
         /*
         Можно было бы использовать только 1 ... 12 в `match`,
             но какого возраста тогда был бы ребёнок? Вместо этого мы
             привязываем `n` к последовательности 1 .. 12.
             Теперь мы можем сообщить возраст.
         */
-        n @ 1..=12 => println!("Я ребёнок. Мне {:?}", n),
-        n @ 13..=19 => println!("Я подросток. Мне {:?}", n),
+        n @ 1..=12 => writeln!(out, "Я ребёнок. Мне {:?}", n),
+        n @ 13..=19 => writeln!(out, "Я подросток. Мне {:?}", n),
         // Ничего не привязываем
-        n => println!("Я взрослый. Мне {:?}", n),
+        n => writeln!(out, "Я взрослый. Мне {:?}", n),
     }
+    // >> Скажи мне свой возраст
+    // >> Я подросток. Мне 15
 
     match some_number() {
         // Вариант `Some`, выбираем, если его значение, привязанное к `n`, равно 42.
-        Some(n @ 42) => println!("Ответ: {}!", n),
+        Some(n @ 42) => writeln!(out, "Ответ: {}!", n),
         // При других числах.
-        Some(n) => println!("Не интересно... {}", n),
+        Some(n) => writeln!(out, "Не интересно... {}", n),
         // Для всего остального (вариант `None`).
         _ => (),
     }
+    // >> Ответ: 42!
 }
 
 // 8.6 if let
 // В некоторых случаях использование match выглядит неуклюже. Например:
 
-fn if_let() {
+/// `if let` вместо `match` с одной интересной ветвью.
+pub fn if_let(out: &Output) {
     let optional = Some(7);
 
+    #[allow(clippy::single_match)]
     match optional {
         Some(i) => {
-            println!("Это очень большая строка и {:?}", i)
+            writeln!(out, "Это очень большая строка и {:?}", i)
             // ^ Нужно 2 отступа только для того, чтобы извлечь `i`
+            // >> Это очень большая строка и 7
         }
         _ => {} // ^ Обязателен, так как `match` исчерпывающий. Не выглядит ли это как зря потраченное пространство?
     }
@@ -515,16 +612,16 @@ fn if_let() {
     `"Если `let` деструктуризирует `number` в `Some(i)`, выполнить блок (`{}`).
     */
     if let Some(i) = number {
-        println!("Соответствует {:?}!", i);
+        writeln!(out, "Соответствует {:?}!", i);
         // >> Соответствует 7!
     }
 
     // Если нужно указать, что делать, в случае ошибки, можно добавить else:
     if let Some(i) = letter {
-        print!("Соответствует {:?}", i);
+        write!(out, "Соответствует {:?}", i);
     } else {
         // Ошибка деструктуризации. Переходим к обработке ошибки.
-        println!("Не соответветствует числу. Давайте попробуем строку!");
+        writeln!(out, "Не соответветствует числу. Давайте попробуем строку!");
         // >> Не соответветствует числу. Давайте попробуем строку!
     }
 
@@ -532,13 +629,17 @@ fn if_let() {
     let i_like_letters = false;
 
     if let Some(i) = emoticon {
-        println!("Соответствует {:?}!", i);
+        writeln!(out, "Соответствует {:?}!", i);
     // Оцените условие `else if`, чтобы увидеть, должна ли быть альтернативная ветка отказа:
     } else if i_like_letters {
-        println!("Не соответствует числу. Давайте попробуем строку!");
+        writeln!(out, "Не соответствует числу. Давайте попробуем строку!");
     } else {
         // Рассматриваем ложное условие. Эта ветвь по умолчанию:
-        println!("Мне не нравиться сравнивать строки. Давайте возьмём смайлик :)!");
+        writeln!(
+            out,
+            "Мне не нравиться сравнивать строки. Давайте возьмём смайлик :)!"
+        );
+        // >> Мне не нравиться сравнивать строки. Давайте возьмём смайлик :)!
     }
 
     // Точно так же, if let может быть использован для сравнения любого значения перечисления:
@@ -558,13 +659,13 @@ fn if_let() {
 
     // Переменная `a` соответствует `Foo::Bar`
     if let Foo::Bar = a {
-        println!("a = Foo::Bar");
+        writeln!(out, "a = Foo::Bar");
         // >> a = Foo::Bar
     }
 
     // Переменная `b` не соответствует `Foo::Bar`. Поэтому ничего не выведется на экран
     if let Foo::Bar = b {
-        println!("b = Foo::Bar, b = {:?}", b);
+        writeln!(out, "b = Foo::Bar, b = {:?}", b);
     }
 
     /*
@@ -572,14 +673,14 @@ fn if_let() {
         аналогичное `Some()` как в предыдущем примере:
     */
     if let Foo::Qux(value) = c {
-        println!("c = {}", value);
-        //  c = 100
+        writeln!(out, "c = {}", value);
+        // >> c = 100
     }
 
     // С `if let` также работает и привязка
     if let Foo::Qux(value @ 100) = c {
-        println!("c = 100, {}", value);
-        // c = 100, 100
+        writeln!(out, "c = 100, {}", value);
+        // >> c = 100, 100
     }
 
     /*
@@ -597,7 +698,7 @@ fn if_let() {
     // Переменная соответствует Foo::Bar
     // if Foo::Bar == a {
     //     // ^-- это вызовет ошибку компиляции. Используйте `if let` вместо этого.
-    //     println!("a is foobar");
+    //     writeln!(out, "a is foobar");
     // }
 }
 
@@ -606,18 +707,20 @@ fn if_let() {
 Так же, как иif let, while let может сделать неудобный match более терпимым.
 */
 
-fn while_let() {
+/// `while let`: цикл, пока значение подходит под шаблон.
+pub fn while_let(out: &Output) {
     let mut optional = Some(0);
 
+    #[allow(clippy::while_let_loop)]
     loop {
         match optional {
             // Если `optional` деструктурируется, выполним следующий блок.
             Some(i) => {
                 if i > 9 {
-                    println!("Больше 9, уходим отсюда!");
+                    writeln!(out, "Больше 9, уходим отсюда!");
                     optional = None;
                 } else {
-                    println!("`i` равен `{:?}`. Попробуем ещё раз.", i);
+                    writeln!(out, "`i` равен `{:?}`. Попробуем ещё раз.", i);
                     optional = Some(i + 1);
                 }
                 // ^ Требует 3 уровня вложенности!
@@ -638,10 +741,10 @@ fn while_let() {
     */
     while let Some(i) = optional {
         if i > 9 {
-            println!("Больше 9, уходим отсюда!");
+            writeln!(out, "Больше 9, уходим отсюда!");
             optional = None;
         } else {
-            println!("`i` равен `{:?}`. Попробуем ещё раз.", i);
+            writeln!(out, "`i` равен `{:?}`. Попробуем ещё раз.", i);
             optional = Some(i + 1)
         }
         // ^ Меньше смещаемся вправо, к тому же нет необходимости обрабатывать ошибки.
@@ -649,20 +752,42 @@ fn while_let() {
     // ^ К `if let` можно добавить дополнительный блок `else`/`else if`. Для `while let` подобного нет.
 }
 
-pub fn run8() {
-    if_else();
-    loop_();
-    nesting_and_labels();
-    return_loop();
-    while_();
-    for_();
-    _mathc();
-    match_tuple();
-    match_enum();
-    match_link_and_signposts();
-    match_struct();
-    match_limited();
-    match_tiung();
-    if_let();
-    while_let();
+/// Глава целиком: все разделы подряд, без заголовков.
+pub fn run8(out: &Output) {
+    if_else(out);
+    loop_(out);
+    nesting_and_labels(out);
+    return_loop(out);
+    while_(out);
+    for_(out);
+    _mathc(out);
+    match_tuple(out);
+    match_enum(out);
+    match_link_and_signposts(out);
+    match_struct(out);
+    match_limited(out);
+    match_tiung(out);
+    if_let(out);
+    while_let(out);
 }
+
+/// Разделы главы 8 в порядке книги.
+pub const SECTIONS: &[Section] = &[
+    heading!("8", "Управление потоком"),
+    section!("8.1", "if/else", if_else),
+    section!("8.2", "loop", loop_),
+    section!("8.2.1", "Вложенность и метки", nesting_and_labels),
+    section!("8.2.2", "Возврат из циклов", return_loop),
+    section!("8.3", "while", while_),
+    section!("8.4", "Цикл for и range", for_),
+    section!("8.5", "Match", _mathc),
+    heading!("8.5.1", "Деструктуризация"),
+    section!("8.5.1.1", "Кортежи", match_tuple),
+    section!("8.5.1.2", "Перечисления", match_enum),
+    section!("8.5.1.3", "Указатели и ссылки", match_link_and_signposts),
+    section!("8.5.1.4", "Структуры", match_struct),
+    section!("8.5.2", "Ограничители шаблонов", match_limited),
+    section!("8.5.3", "Связывание", match_tiung),
+    section!("8.6", "if let", if_let),
+    section!("8.7", "while let", while_let),
+];
@@ -1,43 +1,127 @@
-mod getting_started;
-use getting_started::run1;
+mod cli;
 
-mod primitives;
-use primitives::run2;
+use std::process::ExitCode;
 
-mod use_type;
-use use_type::run3;
+use start::book::{self, Section, Selection};
+use start::i18n::{self, Lang};
+use start::progress::{self, Progress};
+use start::{
+    bench, bless, check, edit, errors, explain, export, interactive, quiz, report, runner, search,
+    serve, toc, tr, tui, verify, watch,
+};
 
-mod variable_binding;
-use variable_binding::run4;
+use cli::Command;
 
-mod types;
-use types::run5;
+// Считает выделения памяти для `start --alloc`
+#[global_allocator]
+static ALLOCATOR: start::alloc::Counting = start::alloc::Counting;
 
-mod conversion;
-use conversion::run6;
+fn main() -> ExitCode {
+    runner::install_panic_hook();
+    i18n::set_lang(Lang::from_env());
+    let command = cli::lang(std::env::args().skip(1).collect()).and_then(|(lang, args)| {
+        if let Some(lang) = lang {
+            i18n::set_lang(lang);
+        }
+        cli::parse(args)
+    });
+    let command = match command {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!("{}\n\n{}", tr!(ERROR, err), tr!(USAGE));
+            return ExitCode::from(2);
+        }
+    };
 
-mod expressions;
-use expressions::run7;
-
-mod loop_control;
-use loop_control::run8;
-
-mod func;
-use func::run9;
-
-mod mods;
+    match dispatch(command) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("{}", tr!(ERROR, err));
+            ExitCode::FAILURE
+        }
+    }
+}
 
-use mods::run10;
+// Выполняет команду. `Ok(false)` — команда отработала, но нашла проблемы
+// (раздел запаниковал, вывод разошёлся с аннотациями и так далее), о которых уже сообщила сама.
+fn dispatch(command: Command) -> Result<bool, String> {
+    match command {
+        Command::Help => println!("{}", tr!(USAGE)),
+        Command::Toc(selection) => {
+            toc::print(&selection.select(book::sections()), &Progress::load())
+        }
+        Command::Progress => progress::print(&book::sections(), &Progress::load())?,
+        Command::Search(query) => return Ok(search::search(&book::sections(), &query)),
+        Command::Explain(selection, options) => explain::explain(&select(&selection)?, &options),
+        Command::Export(selection, format, dir) => {
+            export::export(&select(&selection)?, format, &dir)?
+        }
+        Command::Serve(selection, port) => serve::serve(&select(&selection)?, port)?,
+        Command::Quiz(selection, options) => quiz::quiz(&select(&selection)?, &options),
+        Command::Bench(selection, options) => {
+            let sections = if selection.is_empty() {
+                bench::default_sections(book::sections())
+            } else {
+                select(&selection)?
+            };
+            return Ok(bench::bench(&sections, &options));
+        }
+        Command::Interactive(selection) => {
+            let sections = select(&selection)?;
+            interactive::interactive(&sections, &mut Progress::load());
+        }
+        Command::Tui(selection) => tui::tui(&select(&selection)?, &mut Progress::load())?,
+        Command::Edit(number, options) => {
+            let section = book::sections()
+                .into_iter()
+                .find(|section| section.number() == number)
+                .ok_or(tr!(NOTHING_SELECTED))?;
+            return edit::edit(section, &options);
+        }
+        Command::Check(selection) => {
+            let exercises = check::select(&selection);
+            if exercises.is_empty() {
+                return Err(tr!(NO_EXERCISES).to_string());
+            }
+            let mut progress = Progress::load();
+            let solved = check::check(&exercises, &mut progress);
+            progress.save();
+            return Ok(solved);
+        }
+        Command::Errors(selection, show) => {
+            let errors = errors::select(&selection);
+            if errors.is_empty() {
+                return Err(tr!(NO_COMPILE_ERRORS).to_string());
+            }
+            return errors::errors(&errors, show);
+        }
+        Command::Watch(selection, args) => {
+            select(&selection)?;
+            watch::watch(&selection, &args)?
+        }
+        Command::LangCheck => return Ok(i18n::check(&book::sections())),
+        Command::Verify(selection) => return Ok(verify::verify(&select(&selection)?)),
+        Command::Bless(selection) => return Ok(bless::bless(&select(&selection)?)),
+        Command::Run(selection, options) => {
+            let sections = select(&selection)?;
+            if options.json {
+                return Ok(report::json(&sections, &options));
+            }
+            let mut progress = Progress::load();
+            let result = runner::run(&sections, &options, &mut progress);
+            progress.save();
+            return result;
+        }
+    }
+    Ok(true)
+}
 
-fn main() {
-    run1();
-    run2();
-    run3();
-    run4();
-    run5();
-    run6();
-    run7();
-    run8();
-    run9();
-    run10();
+// Выбранные разделы; если не выбран ни один, команде нечего делать
+fn select(selection: &Selection) -> Result<Vec<&'static Section>, String> {
+    let sections = selection.select(book::sections());
+    if sections.is_empty() {
+        return Err(tr!(NOTHING_SELECTED).to_string());
+    }
+    Ok(sections)
 }
@@ -1,8 +1,17 @@
+//! Глава 10 «Модули»: пока только заголовок, разделов с кодом в ней нет.
+
+use crate::book::Section;
+use crate::output::Output;
+
 // 10 Модули
 /*
-Rust предоставляет мощную систему модулей, 
+Rust предоставляет мощную систему модулей,
     которая используется для иерархического разделения кода на логические единицы (модули) и управления видимостью (публичное и приватное) между ними.
 Модуль - это набор элементов, таких как: функции, структуры, типажи, блоки реализации (impl) и даже другие модули.
 */
 
-pub fn run10() {}
+/// Глава целиком: все разделы подряд, без заголовков.
+pub fn run10(_out: &Output) {}
+
+/// Разделы главы 10 в порядке книги.
+pub const SECTIONS: &[Section] = &[heading!("10", "Модули")];
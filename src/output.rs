@@ -0,0 +1,136 @@
+//! Поток, в который разделы пишут свой вывод: stdout, буфер, файл или тест.
+//!
+//! Разделы принимают `&Output`, а не `&mut dyn Write`: замыкания из главы 9
+//! захватывают `out` по неизменяемой ссылке и поэтому остаются `Fn`, как и с `println!`.
+
+use std::cell::RefCell;
+use std::fmt;
+use std::io::{self, Write};
+use std::thread;
+
+use crate::alloc;
+use crate::i18n::{self, Lang};
+
+/// Вывод раздела: `writeln!(out, ...)` пишет в поток, переданный в [`Output::new`].
+pub struct Output<'a> {
+    sink: RefCell<&'a mut dyn Write>,
+    // В английском режиме — текст каждого вызова `write!` по-русски. Он переводится
+    // не сразу, а в `flush`: перевод не должен попадать в замеры времени и памяти раздела.
+    pending: RefCell<Vec<String>>,
+}
+
+impl<'a> Output<'a> {
+    /// Вывод в `sink`.
+    pub fn new(sink: &'a mut dyn Write) -> Output<'a> {
+        Output {
+            sink: RefCell::new(sink),
+            pending: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Вызывается макросами `write!` и `writeln!`: `writeln!(out, "{}", x)`.
+    /// Как и `println!`, паникует, если записать вывод не удалось.
+    pub fn write_fmt(&self, args: fmt::Arguments<'_>) {
+        match i18n::lang() {
+            Lang::Ru => self
+                .sink
+                .borrow_mut()
+                .write_fmt(args)
+                .expect("не удалось записать вывод раздела"),
+            Lang::En => {
+                // Выделения самого раздела (например, в его `Display`) считаются как обычно,
+                // а рост буфера, которого в русском режиме нет, — нет
+                let mut text = String::new();
+                fmt::write(&mut Uncounted(&mut text), args).unwrap();
+                alloc::uncounted(|| self.pending.borrow_mut().push(text));
+            }
+        }
+    }
+
+    /// Переводит по каталогу `i18n/output.txt` и записывает отложенный английский вывод.
+    /// Вызывается и при удалении `Output`, поэтому явно — только там, где вывод нужен раньше.
+    pub fn flush(&self) -> io::Result<()> {
+        let pending = self.pending.take();
+        let mut sink = self.sink.borrow_mut();
+        let result = pending
+            .iter()
+            .try_for_each(|text| sink.write_all(i18n::output(text).as_bytes()));
+        alloc::uncounted(|| drop(pending));
+        result
+    }
+}
+
+impl Drop for Output<'_> {
+    fn drop(&mut self) {
+        let result = self.flush();
+        // Во время паники вторая паника остановила бы программу
+        if !thread::panicking() {
+            result.expect("не удалось записать вывод раздела");
+        }
+    }
+}
+
+// Буфер, рост которого не попадает в счётчики `--alloc`
+struct Uncounted<'a>(&'a mut String);
+
+impl fmt::Write for Uncounted<'_> {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        alloc::uncounted(|| self.0.push_str(text));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::book;
+    use crate::runner;
+
+    #[test]
+    fn writes_into_the_sink() {
+        let mut buffer = Vec::new();
+        {
+            let out = Output::new(&mut buffer);
+            writeln!(out, "{} + {} = {}", 1, 2, 3);
+            write!(out, "без перевода строки");
+        }
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "1 + 2 = 3\nбез перевода строки"
+        );
+    }
+
+    #[cfg(feature = "ch-formatting")]
+    #[test]
+    fn captures_a_section_in_process() {
+        let hello = book::sections()
+            .into_iter()
+            .find(|section| section.number == "1")
+            .unwrap();
+        assert_eq!(
+            runner::capture(hello).unwrap(),
+            "Привет, мир!\nЯ программирую на языке Rust!\n"
+        );
+    }
+
+    // Паника раздела возвращается сообщением с местом в коде, а не прерывает проверку
+    #[test]
+    fn reports_a_panic_as_an_error() {
+        fn section(out: &Output) {
+            writeln!(out, "до паники");
+            panic!("стоп");
+        }
+
+        let section = book::Section {
+            number: "1",
+            title: "",
+            path: "start::tests::section",
+            file: "src/tests.rs",
+            source: "",
+            run: Some(section),
+            should_panic: false,
+        };
+        let message = runner::capture(&section).unwrap_err();
+        assert!(message.starts_with("стоп (src/output.rs:"), "{}", message);
+    }
+}
@@ -1,6 +1,12 @@
+//! Глава 2 «Примитивы»: скалярные типы, литералы и операторы, кортежи, массивы и срезы.
+
 // Атрибут, который убирает предупреждения компилятора о неиспользуемом коде
-#![allow(dead_code)]
 #![allow(unused_variables)]
+#![allow(unused_assignments)]
+
+use crate::book::Section;
+use crate::output::Output;
+
 // 2 Примитивы
 
 /*
@@ -43,7 +49,8 @@ Mассивы, например [1, 2, 3]
 Kортежи, например (1, true)
 */
 
-fn primitives() {
+/// Скалярные типы: аннотации, суффиксы, типы по умолчанию, вывод типов и затенение.
+pub fn primitives(out: &Output) {
     let logical = true;
     // Обычная аннотация
     let a_float: f64 = 1.0;
@@ -70,21 +77,23 @@ fn primitives() {
     // Переменные могут быть переопределены с помощью затенения.
     let mutable = true;
 
-    println!(
+    writeln!(
+        out,
         "Дробные: logical={}, default_float{}",
         a_float, default_float
     );
     // >> Дробные: logical=1, default_float3
-    println!(
+    writeln!(
+        out,
         "Целые: an_integer={}, default_integer={}, inferred_type={}",
         an_integer, default_integer, inferred_type
     );
     // >> Целые: an_integer=5, default_integer=6, inferred_type=4294967296
-    println!("Логические: logical={}, mutable={}", logical, mutable);
+    writeln!(out, "Логические: logical={}, mutable={}", logical, mutable);
     // >> Логические: logical=true, mutable=true
 }
 
-// Литералы и операторы
+// 2.1 Литералы и операторы
 
 /*
 Целочисленные значения так же могут быть выражены с помощью:
@@ -99,39 +108,41 @@ fn primitives() {
 Сейчас мы используем суффикс u32, чтобы указать, что литерал - беззнаковое целое число 32-х бит и суффикс i32 - знаковое целое 32-х битное число.
  */
 
-fn litetal_and_operator() {
+/// Целочисленные и логические операторы, побитовые сдвиги и разделитель `_` в литералах.
+#[allow(clippy::identity_op, clippy::nonminimal_bool)]
+pub fn litetal_and_operator(out: &Output) {
     // Aрифметические операции
-    println!("1 + 2 = {}", 1i32 + 2);
+    writeln!(out, "1 + 2 = {}", 1i32 + 2);
     // >> 1 + 2 = 3
-    println!("1 - 2 = {}", 1i32 - 2);
+    writeln!(out, "1 - 2 = {}", 1i32 - 2);
     // >> 1 - 2 = -1
-    println!("1 * 2 = {}", 1i32 * 2);
+    writeln!(out, "1 * 2 = {}", 1i32 * 2);
     // >> 1 * 2 = 2
     // Операции деления будет работь для целых чисел до целых чисел
-    println!("4 / 2 = {}", 4i32 / 2);
+    writeln!(out, "4 / 2 = {}", 4i32 / 2);
     // >> 4 / 2 = 2
     // Операции деления будет работь для дробных чисел с дробью
-    println!("4.2 / 2.1 = {}", 4f32 / 2.1f32);
+    writeln!(out, "4.2 / 2.1 = {}", 4f32 / 2.1f32);
     // >> 4.2 / 2.1 = 1.904762
-    println!("10 % 3 = {}", 10i8 % 3);
+    writeln!(out, "10 % 3 = {}", 10i8 % 3);
     // >> 10 % 3 = 1
 
     // Арифметические операции + присваивание
     let mut n = 10;
     n += 1;
-    println!("n += 1 = {}", n);
+    writeln!(out, "n += 1 = {}", n);
     // >> n += 1 = 11
     n -= 1;
-    println!("n -= 1 = {}", n);
+    writeln!(out, "n -= 1 = {}", n);
     // >> n -= 1 = 10
     n *= 2;
-    println!("n *= 2 = {}", n);
-    // >> n -= 1 = 10
+    writeln!(out, "n *= 2 = {}", n);
+    // >> n *= 2 = 20
     n /= 2;
-    println!("n /= 2 = {}", n);
+    writeln!(out, "n /= 2 = {}", n);
     // >> n /= 2 = 10
     n %= 6;
-    println!("n %=6 = {}", n);
+    writeln!(out, "n %=6 = {}", n);
     // >> n %=6 = 4
 
     /*
@@ -140,11 +151,11 @@ fn litetal_and_operator() {
         && - И
         || - ИЛИ
     */
-    println!("true И fasle будет {}", true && false);
+    writeln!(out, "true И fasle будет {}", true && false);
     // >> true И fasle будет false
-    println!("true ИЛИ false будет {}", true || false);
+    writeln!(out, "true ИЛИ false будет {}", true || false);
     // >> true ИЛИ false будет true
-    println!("НЕ true будет {}", !true);
+    writeln!(out, "НЕ true будет {}", !true);
     // >> НЕ true будет false
 
     /*
@@ -155,20 +166,24 @@ fn litetal_and_operator() {
     << - Левый сдвиг - это бинарный оператор, который принимает два числа, сдвигает влево биты первого операнда, а второй операнд определяет количество позиций для сдвига
     >> - Правый сдвиг - это бинарный оператор, который принимает два числа, сдвигает вправо биты первого операнда, а второй операнд определяет количество позиций для сдвига
     */
-    println!("0011 и 0101 будет {:04b}", 0b0011u32 & 0b0101);
+    writeln!(out, "0011 и 0101 будет {:04b}", 0b0011u32 & 0b0101);
     // >> 0011 и 0101 будет 0001
-    println!("0011 ИЛИ 0101 будет {:04b}", 0b0011u32 | 0b0101);
+    writeln!(out, "0011 ИЛИ 0101 будет {:04b}", 0b0011u32 | 0b0101);
     // >> 0011 ИЛИ 0101 будет 0111
-    println!("0011 исключающее ИЛИ 0101 будет {:04b}", 0b0011u32 ^ 0b0101);
+    writeln!(
+        out,
+        "0011 исключающее ИЛИ 0101 будет {:04b}",
+        0b0011u32 ^ 0b0101
+    );
     // >> 0011 исключающее ИЛИ 0101 будет 0110
-    println!("1 << 5 будет {}", 1u32 << 5);
+    writeln!(out, "1 << 5 будет {}", 1u32 << 5);
     // >> 1 << 5 будет 32
-    println!("0x80 >> 2 будет 0x{:x}", 0x80u32 >> 2);
+    writeln!(out, "0x80 >> 2 будет 0x{:x}", 0x80u32 >> 2);
     // >> 0x80 >> 2 будет 0x20
 
     // Использование подчёркивания для улучшения читаемости!
-    println!("Один миллион записан как {}", 1_000_000u32);
-    // >>
+    writeln!(out, "Один миллион записан как {}", 1_000_000u32);
+    // >> Один миллион записан как 1000000
 }
 
 // 2.2 Кортежи
@@ -186,8 +201,9 @@ fn reverse(pair: (i32, bool)) -> (bool, i32) {
     (boolean, integer)
 }
 
+/// Матрица 2×2, записанная по строкам: `Matrix(a, b, c, d)` — это `(a, b)` над `(c, d)`.
 #[derive(Debug)]
-struct Matrix(f32, f32, f32, f32);
+pub struct Matrix(pub f32, pub f32, pub f32, pub f32);
 
 impl fmt::Display for Matrix {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -196,59 +212,61 @@ impl fmt::Display for Matrix {
 }
 
 impl Matrix {
-    fn transpose(&self) -> Matrix {
+    /// Транспонированная матрица: элементы меняются местами относительно главной диагонали.
+    pub fn transpose(&self) -> Matrix {
         Matrix(self.0, self.2, self.1, self.3)
     }
 }
 
-fn tuple() {
+/// Кортежи: доступ по индексу, вложенность, деструктуризация и кортеж из одного элемента.
+pub fn tuple(out: &Output) {
     let long_tuple = (
         1u8, 2u16, 2u32, 4u64, -1i8, -2i16, -3i32, -4i64, 0.1f32, 0.2f64, 'a', true,
     );
     // К значениям переменных внутри кортежа можно обратиться по индексу
-    println!("Первое значение длинного кортежа: {}", long_tuple.0);
+    writeln!(out, "Первое значение длинного кортежа: {}", long_tuple.0);
     // >> Первое значение длинного кортежа: 1
-    println!("Второе значение длинного кортежа: {}", long_tuple.1);
+    writeln!(out, "Второе значение длинного кортежа: {}", long_tuple.1);
     // >> Второе значение длинного кортежа: 2
 
     // Кортежи могут содержать в себе кортежи
     let tuple_of_tuple = ((1u8, 2u16, 2u32), (4u64, -1i8), -2i16);
     // Но длинные Кортежи не могут быть напечатаны
-    println!("Кортеж из кортежей {:?}", tuple_of_tuple);
+    writeln!(out, "Кортеж из кортежей {:?}", tuple_of_tuple);
     // >> Кортеж из кортежей ((1, 2, 2), (4, -1), -2)
 
     let pair = (1, true);
-    println!("Pair хранит в себе {:?}", pair);
+    writeln!(out, "Pair хранит в себе {:?}", pair);
     // >> Pair хранит в себе (1, true)
 
-    println!("Перевёрнутая pair будет {:?}", reverse(pair));
+    writeln!(out, "Перевёрнутая pair будет {:?}", reverse(pair));
     // >> Перевёрнутая pair будет (true, 1)
 
     // Для создания кортежа, содержащего один элемент, необходимо написать элемент и
     // поставить запятую внутри круглых скобок.
-    println!("Кортеж из одного элемента: {:?}", (5u32,));
+    writeln!(out, "Кортеж из одного элемента: {:?}", (5u32,));
     // >> Кортеж из одного элемента: (5,)
-    println!("Просто целочисленное значение: {:?}", (5u32));
+    writeln!(out, "Просто целочисленное значение: {:?}", (5u32));
     // >> Просто целочисленное значение: 5
 
     // Кортежи можно разобрать на части (деструктурировать) для создания связи
     let tuple = (1, "привет", 4.5, true);
 
     let (a, b, c, d) = tuple;
-    println!("{}, {}, {}, {}", a, b, c, d);
-    // >> 1, "привет", 4.5, true
+    writeln!(out, "{}, {}, {}, {}", a, b, c, d);
+    // >> 1, привет, 4.5, true
 
     // Определение кортежной структуры
     let matrix = Matrix(1.1, 1.2, 2.1, 2.2);
-    println!("{:?}", matrix);
-    // >> Matrix(1.1, 1.2, 2.1, 2.2
-    println!("Matrix:\n{}", matrix);
+    writeln!(out, "{:?}", matrix);
+    // >> Matrix(1.1, 1.2, 2.1, 2.2)
+    writeln!(out, "Matrix:\n{}", matrix);
     /*
     >> Matrix:
     (1.1, 1.2)
     (2.1, 2.2)
     */
-    println!("Transpose:\n{}", matrix.transpose());
+    writeln!(out, "Transpose:\n{}", matrix.transpose());
     /*
     >> Transpose:
     (1.1, 2.1)
@@ -270,34 +288,35 @@ fn tuple() {
 
 use ::std::mem;
 
-fn analyze_slice(slice: &[i32]) {
-    println!("Первый элемент среза: {}", slice[0]);
-    println!("В срезе {} элементов", slice.len());
+fn analyze_slice(out: &Output, slice: &[i32]) {
+    writeln!(out, "Первый элемент среза: {}", slice[0]);
+    writeln!(out, "В срезе {} элементов", slice.len());
 }
 
-fn arr_and_slice() {
+/// Массивы фиксированной длины и срезы, заимствующие их части.
+pub fn arr_and_slice(out: &Output) {
     // Массив фиксированного размера (указывать сигнатуру типа необязательно)
     let xs: [i32; 5] = [1, 2, 3, 4, 5];
     let ys = [0; 500];
 
     // Индекс начинается с 0
-    println!("Первый элемент массива: {}", xs[0]);
+    writeln!(out, "Первый элемент массива: {}", xs[0]);
     // >> Первый элемент массива: 1
-    println!("Второй элеменгт массива: {}", xs[1]);
-    // Второй элеменгт массива: 2
+    writeln!(out, "Второй элеменгт массива: {}", xs[1]);
+    // >> Второй элеменгт массива: 2
 
     // `len` возвращает длину массива
-    println!("Размер массива: {}", xs.len());
+    writeln!(out, "Размер массива: {}", xs.len());
     // >> Размер массива: 5
 
-    println!("Массив занимает {} байт", mem::size_of_val(&xs));
+    writeln!(out, "Массив занимает {} байт", mem::size_of_val(&xs));
     // >> Массив занимает 20 байт
-    println!("Массив занимает {} байт", mem::size_of_val(&ys));
+    writeln!(out, "Массив занимает {} байт", mem::size_of_val(&ys));
     // >> Массив занимает 2000 байт
 
     // Заимствуем весь массив как срез
     // Массивы могут быть автоматически заимствованы как срез
-    analyze_slice(&xs);
+    analyze_slice(out, &xs);
     /*
     >> Первый элемент среза: 1
     >> В срезе 5 элементов
@@ -310,16 +329,25 @@ fn arr_and_slice() {
         ending_index — на 1 больше, чем последняя позиция в срезе
     */
     // Заимствуем часть массива как срез
-    analyze_slice(&ys[1..4]);
+    analyze_slice(out, &ys[1..4]);
     /*
     >> Первый элемент среза: 0
     >> В срезе 3 элементов
     */
 }
 
-pub fn run2() {
-    primitives();
-    litetal_and_operator();
-    tuple();
-    arr_and_slice();
+/// Глава целиком: все разделы подряд, без заголовков.
+pub fn run2(out: &Output) {
+    primitives(out);
+    litetal_and_operator(out);
+    tuple(out);
+    arr_and_slice(out);
 }
+
+/// Разделы главы 2 в порядке книги.
+pub const SECTIONS: &[Section] = &[
+    section!("2", "Примитивы", primitives),
+    section!("2.1", "Литералы и операторы", litetal_and_operator),
+    section!("2.2", "Кортежи", tuple),
+    section!("2.3", "Массивы и срезы", arr_and_slice),
+];
@@ -0,0 +1,374 @@
+// Прогресс студента: какие разделы запускались и какие задания решены, и когда.
+// Хранится в TOML-файле в каталоге настроек пользователя, чтобы курс можно было
+// проходить несколько недель и продолжать с места остановки.
+//
+// Формат файла:
+//
+//     [sections."1.2.2"]
+//     passed = true
+//     time = 1760781600
+//
+//     [exercises."1.2.2"]
+//     passed = false
+//     time = 1760781700
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::book::Section;
+use crate::exercises::EXERCISES;
+use crate::tr;
+
+// Последний результат раздела или задания
+#[derive(Clone, Copy)]
+pub struct Entry {
+    pub passed: bool,
+    // Секунды с начала эпохи Unix
+    pub time: u64,
+}
+
+#[derive(Default)]
+pub struct Progress {
+    path: Option<PathBuf>,
+    sections: BTreeMap<String, Entry>,
+    exercises: BTreeMap<String, Entry>,
+}
+
+impl Progress {
+    // Читает сохранённый прогресс. Если файла ещё нет, прогресс пустой.
+    pub fn load() -> Progress {
+        let mut progress = Progress {
+            path: path(),
+            ..Progress::default()
+        };
+        let Some(path) = progress.path.clone() else {
+            return progress;
+        };
+        let Ok(text) = fs::read_to_string(&path) else {
+            return progress;
+        };
+        if let Err(err) = progress.parse(&text) {
+            eprintln!("{}", tr!(PROGRESS_UNREADABLE, path.display(), err));
+            progress.sections.clear();
+            progress.exercises.clear();
+        }
+        progress
+    }
+
+    // Ошибка записи не прерывает работу: прогресс — вспомогательная функция
+    pub fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        let result = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|_| fs::write(path, self.to_toml()));
+        if let Err(err) = result {
+            eprintln!("{}", tr!(PROGRESS_NOT_SAVED, path.display(), err));
+        }
+    }
+
+    pub fn record_section(&mut self, number: &str, passed: bool) {
+        self.sections.insert(number.to_string(), Entry::now(passed));
+    }
+
+    pub fn record_exercise(&mut self, section: &str, passed: bool) {
+        self.exercises
+            .insert(section.to_string(), Entry::now(passed));
+    }
+
+    pub fn section(&self, number: &str) -> Option<Entry> {
+        self.sections.get(number).copied()
+    }
+
+    pub fn exercise(&self, section: &str) -> Option<Entry> {
+        self.exercises.get(section).copied()
+    }
+
+    // Отметка раздела для оглавления: ✗ — если раздел упал или задание к нему не решено,
+    // ✓ — если раздел запускался, пробел — если ещё не начат
+    pub fn mark(&self, section: &Section) -> char {
+        let exercise_failed = EXERCISES
+            .iter()
+            .filter(|exercise| exercise.section == section.number)
+            .any(|exercise| self.exercise(exercise.section).is_some_and(|e| !e.passed));
+        match self.section(section.number) {
+            _ if exercise_failed => '✗',
+            Some(entry) if !entry.passed => '✗',
+            Some(_) => '✓',
+            None => ' ',
+        }
+    }
+
+    fn parse(&mut self, text: &str) -> Result<(), String> {
+        let mut current: Option<&mut Entry> = None;
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = || tr!(PROGRESS_BAD_LINE, i + 1, line);
+
+            if let Some(header) = line.strip_prefix('[').and_then(|h| h.strip_suffix(']')) {
+                let (table, key) = header.split_once('.').ok_or_else(error)?;
+                let key = key.trim_matches('"').to_string();
+                let entries = match table {
+                    "sections" => &mut self.sections,
+                    "exercises" => &mut self.exercises,
+                    _ => return Err(error()),
+                };
+                current = Some(entries.entry(key).or_insert(Entry {
+                    passed: false,
+                    time: 0,
+                }));
+                continue;
+            }
+
+            let (key, value) = line.split_once('=').ok_or_else(error)?;
+            let entry = current.as_mut().ok_or_else(error)?;
+            match key.trim() {
+                "passed" => entry.passed = value.trim().parse().map_err(|_| error())?,
+                "time" => entry.time = value.trim().parse().map_err(|_| error())?,
+                _ => return Err(error()),
+            }
+        }
+        Ok(())
+    }
+
+    fn to_toml(&self) -> String {
+        let mut text = String::from("# Прогресс по книге `start`\n");
+        for (table, entries) in [("sections", &self.sections), ("exercises", &self.exercises)] {
+            for (key, entry) in entries {
+                writeln!(
+                    text,
+                    "\n[{}.\"{}\"]\npassed = {}\ntime = {}",
+                    table, key, entry.passed, entry.time
+                )
+                .unwrap();
+            }
+        }
+        text
+    }
+}
+
+impl Entry {
+    fn now(passed: bool) -> Entry {
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+        Entry { passed, time }
+    }
+}
+
+// `START_PROGRESS` задаёт файл явно, иначе используется каталог настроек ОС:
+// `$XDG_CONFIG_HOME`, `~/.config` или `%APPDATA%`.
+// `START_PROGRESS=off` отключает прогресс: он не читается и не сохраняется.
+fn path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("START_PROGRESS") {
+        return (path != "off").then(|| PathBuf::from(path));
+    }
+    let config = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))?;
+    Some(config.join("start").join("progress.toml"))
+}
+
+// `start progress`: сводка по главам и раздел, с которого стоит продолжить.
+// Закрытый канал (`start progress | head`) — обычное завершение, а не ошибка.
+pub fn print(sections: &[&Section], progress: &Progress) -> Result<(), String> {
+    match write_summary(&mut io::stdout().lock(), sections, progress) {
+        Err(err) if err.kind() != io::ErrorKind::BrokenPipe => Err(err.to_string()),
+        _ => Ok(()),
+    }
+}
+
+fn write_summary(
+    out: &mut impl io::Write,
+    sections: &[&Section],
+    progress: &Progress,
+) -> io::Result<()> {
+    let mut chapters: Vec<(&Section, Vec<&Section>)> = Vec::new();
+    for section in sections {
+        match chapters.last_mut() {
+            Some((_, chapter)) if section.depth() > 1 => chapter.push(section),
+            _ => chapters.push((section, vec![section])),
+        }
+    }
+
+    let width = chapters
+        .iter()
+        .map(|(chapter, _)| {
+            format!("{} {}", chapter.number, chapter.title())
+                .chars()
+                .count()
+        })
+        .max()
+        .unwrap_or(0);
+
+    for (chapter, sections) in &chapters {
+        let runnable: Vec<&&Section> = sections.iter().filter(|s| s.run.is_some()).collect();
+        let entries: Vec<Entry> = runnable
+            .iter()
+            .filter_map(|section| progress.section(section.number))
+            .collect();
+        let passed = entries.iter().filter(|entry| entry.passed).count();
+        let failed = entries.len() - passed;
+
+        let exercises: Vec<Option<Entry>> = EXERCISES
+            .iter()
+            .filter(|exercise| sections.iter().any(|s| s.number == exercise.section))
+            .map(|exercise| progress.exercise(exercise.section))
+            .collect();
+        let solved = exercises
+            .iter()
+            .filter(|entry| entry.is_some_and(|e| e.passed))
+            .count();
+
+        let last = entries
+            .iter()
+            .chain(exercises.iter().flatten())
+            .map(|entry| entry.time)
+            .max();
+
+        let title = format!("{} {}", chapter.number, chapter.title());
+        let mut line = format!(
+            "{}{}  {}",
+            title,
+            " ".repeat(width - title.chars().count()),
+            tr!(PROGRESS_SECTIONS, passed, runnable.len())
+        );
+        if failed > 0 {
+            line.push_str(&tr!(PROGRESS_PANICKED, failed));
+        }
+        if !exercises.is_empty() {
+            line.push_str(&tr!(PROGRESS_EXERCISES, solved, exercises.len()));
+        }
+        if let Some(time) = last {
+            line.push_str(&tr!(PROGRESS_LAST, date(time)));
+        }
+        writeln!(out, "{}", line)?;
+    }
+
+    let next = sections
+        .iter()
+        .filter(|section| section.run.is_some())
+        .find(|section| !progress.section(section.number).is_some_and(|e| e.passed));
+    writeln!(out)?;
+    match next {
+        Some(section) => writeln!(
+            out,
+            "{}",
+            tr!(
+                PROGRESS_CONTINUE,
+                section.number,
+                section.title(),
+                section.number
+            )
+        ),
+        None => writeln!(out, "{}", tr!(PROGRESS_DONE)),
+    }
+}
+
+// Дата `ГГГГ-ММ-ДД` (UTC) по времени Unix
+fn date(time: u64) -> String {
+    // Алгоритм перевода дней от эпохи в григорианскую дату (Howard Hinnant, `civil_from_days`)
+    let days = (time / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(text: &str) -> Result<Progress, String> {
+        let mut progress = Progress::default();
+        progress.parse(text).map(|_| progress)
+    }
+
+    #[test]
+    fn parses_sections_and_exercises() {
+        let progress = parsed(
+            "# комментарий\n\
+             \n\
+             [sections.\"1.2.2\"]\n\
+             passed = true\n\
+             time = 1760781600\n\
+             \n\
+             [exercises.\"1.2.2\"]\n\
+             passed = false\n\
+             time = 1760781700\n",
+        )
+        .unwrap();
+
+        let section = progress.section("1.2.2").unwrap();
+        assert!(section.passed);
+        assert_eq!(section.time, 1760781600);
+        let exercise = progress.exercise("1.2.2").unwrap();
+        assert!(!exercise.passed);
+        assert_eq!(exercise.time, 1760781700);
+        assert!(progress.section("1.1").is_none());
+    }
+
+    #[test]
+    fn reads_back_what_it_writes() {
+        let mut progress = Progress::default();
+        progress.sections.insert(
+            "3.2.1".to_string(),
+            Entry {
+                passed: false,
+                time: 42,
+            },
+        );
+        progress.exercises.insert(
+            "1.2.2".to_string(),
+            Entry {
+                passed: true,
+                time: 7,
+            },
+        );
+
+        let again = parsed(&progress.to_toml()).unwrap();
+        assert_eq!(again.to_toml(), progress.to_toml());
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        // Неизвестная таблица, значение вне таблицы, неизвестный ключ, не число, не `bool`
+        for text in [
+            "[lessons.\"1\"]",
+            "passed = true",
+            "[sections.\"1\"]\nscore = 5",
+            "[sections.\"1\"]\ntime = вчера",
+            "[sections.\"1\"]\npassed = yes",
+            "[sections.\"1\"]\npassed",
+        ] {
+            assert!(parsed(text).is_err(), "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn converts_unix_time_to_date() {
+        assert_eq!(date(0), "1970-01-01");
+        assert_eq!(date(1760781600), "2025-10-18");
+        // 2000 — високосный (делится на 400), 2100 — нет
+        assert_eq!(date(951782400), "2000-02-29");
+        assert_eq!(date(4107542399), "2100-02-28");
+        assert_eq!(date(4107542400), "2100-03-01");
+    }
+}
@@ -0,0 +1,457 @@
+// `start quiz`: викторина "что напечатает этот код?".
+// Вопросы строятся из аннотаций `// >>`: показывается код раздела до вызова `writeln!`
+// без комментариев (в них часто записан ответ), а ответ сверяется с аннотацией.
+// В режиме `--choices` неверные варианты получаются искажением правильного ответа:
+// значения из соседнего кода (исходное число до `as`, прежнее значение затенённой переменной),
+// соседние числа и результаты переполнения `u8`/`i8`.
+
+use std::io::{self, BufRead, Write};
+use std::ops::Range;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::annotations::{self, ELLIPSIS};
+use crate::book::Section;
+use crate::i18n;
+use crate::tr;
+
+// Сколько строк кода перед вызовом показывать в вопросе
+const CONTEXT: usize = 16;
+
+pub struct Options {
+    pub count: usize,
+    pub choices: bool,
+    pub seed: Option<u64>,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            count: 5,
+            choices: false,
+            seed: None,
+        }
+    }
+}
+
+struct Question<'a> {
+    section: &'a Section,
+    // Строки кода перед вызовом и сам вызов: номер строки в файле и текст
+    context: Vec<(usize, &'a str)>,
+    statement: Vec<(usize, &'a str)>,
+    answer: String,
+}
+
+pub fn quiz(sections: &[&Section], options: &Options) {
+    let mut questions: Vec<Question> = sections
+        .iter()
+        .filter(|section| !section.should_panic)
+        .flat_map(|section| questions(section))
+        .collect();
+    if questions.is_empty() {
+        println!("{}", tr!(QUIZ_EMPTY));
+        return;
+    }
+
+    let mut rng = Rng::new(options.seed);
+    rng.shuffle(&mut questions);
+    questions.truncate(options.count);
+
+    let mut input = io::stdin().lock();
+    let mut score = 0;
+    let mut asked = 0;
+    for (n, question) in questions.iter().enumerate() {
+        println!();
+        println!(
+            "{}",
+            tr!(
+                QUIZ_QUESTION,
+                n + 1,
+                questions.len(),
+                question.section.number,
+                question.section.title(),
+                question.section.file
+            )
+        );
+        for (line, text) in &question.context {
+            println!("{:>5} │ {}", line + 1, text);
+        }
+        for (line, text) in &question.statement {
+            println!("{:>5} ▶ {}", line + 1, text);
+        }
+
+        let choices = if options.choices {
+            choices(question, &mut rng)
+        } else {
+            None
+        };
+        match &choices {
+            Some(choices) => {
+                println!("{}", tr!(QUIZ_PROMPT_CHOICES));
+                for (i, choice) in choices.iter().enumerate() {
+                    println!("  {}) {}", i + 1, choice);
+                }
+            }
+            None => println!("{}", tr!(QUIZ_PROMPT)),
+        }
+        print!("> ");
+        io::stdout().flush().unwrap();
+
+        let mut line = String::new();
+        if input.read_line(&mut line).unwrap_or(0) == 0 {
+            println!();
+            break;
+        }
+        asked += 1;
+
+        let answer = line.trim();
+        let correct = match &choices {
+            Some(choices) => answer
+                .parse::<usize>()
+                .ok()
+                .and_then(|i| choices.get(i.wrapping_sub(1)))
+                .is_some_and(|choice| *choice == question.answer),
+            None => is_correct(answer, &question.answer),
+        };
+        if correct {
+            score += 1;
+            println!("{}", tr!(QUIZ_CORRECT));
+        } else {
+            println!("{}", tr!(QUIZ_WRONG, question.answer));
+        }
+    }
+
+    println!();
+    println!("{}", tr!(QUIZ_TOTAL, score, asked));
+}
+
+// Вопросы по разделу: каждый вызов `writeln!`, сразу за которым идёт однострочная аннотация
+fn questions(section: &Section) -> Vec<Question<'_>> {
+    let Some(span) = annotations::fn_span(section.source, section.func_name()) else {
+        return Vec::new();
+    };
+    let lines: Vec<&str> = section.source.lines().collect();
+    let code = code_lines(&lines, span.clone());
+
+    annotations::expected(section)
+        .into_iter()
+        .filter(|annotation| {
+            annotation.text.trim() != ELLIPSIS
+                && !annotation.text.trim().is_empty()
+                && lines[annotation.line].trim().starts_with("// >>")
+        })
+        .filter_map(|annotation| {
+            // Аннотация должна идти сразу за вызовом, а не за другой аннотацией или комментарием
+            let end = annotation.line - 1;
+            if !code.contains(&end) {
+                return None;
+            }
+            let start = (span.start + 1..=end)
+                .rev()
+                .take(8)
+                .find(|&i| lines[i].contains("writeln!(") || lines[i].contains("write!("))?;
+            let context_start = context_start(&lines, &code, span.start, start);
+            Some(Question {
+                section,
+                context: collapse(
+                    code.iter()
+                        .filter(|&&i| (context_start..start).contains(&i))
+                        .map(|&i| (i, lines[i]))
+                        .collect(),
+                ),
+                statement: (start..=end).map(|i| (i, lines[i])).collect(),
+                // Аннотации записаны по-русски; по-английски ответ — их перевод
+                answer: i18n::output(annotation.text.trim()),
+            })
+        })
+        .collect()
+}
+
+// Строки функции без комментариев
+fn code_lines(lines: &[&str], span: Range<usize>) -> Vec<usize> {
+    let mut code = Vec::new();
+    let mut i = span.start;
+    while i < span.end {
+        let line = lines[i].trim();
+        if line.starts_with("/*") {
+            i = annotations::block_end(lines, i);
+        } else if !line.starts_with("//") {
+            code.push(i);
+        }
+        i += 1;
+    }
+    code
+}
+
+// Первая строка контекста перед вызовом на строке `start`: не дальше `CONTEXT` строк
+// от него и на границе инструкции, чтобы вопрос не начинался с середины выражения
+// или с `}` блока, начало которого не показано
+fn context_start(lines: &[&str], code: &[usize], fn_start: usize, start: usize) -> usize {
+    let earliest = start.saturating_sub(CONTEXT).max(fn_start + 1);
+    let nonblank: Vec<usize> = code
+        .iter()
+        .copied()
+        .filter(|&i| fn_start < i && i < start && !lines[i].trim().is_empty())
+        .collect();
+    let mut first = start;
+    // Блоки, конец которых уже в окне, а начало ещё нет
+    let mut unclosed = 0usize;
+    for (k, &i) in nonblank.iter().enumerate().rev() {
+        if i < earliest {
+            break;
+        }
+        let line = lines[i];
+        unclosed = (unclosed + line.matches('}').count()).saturating_sub(line.matches('{').count());
+        // Предыдущая строка кода — сигнатура функции или конец другой инструкции
+        let after_statement = k
+            .checked_sub(1)
+            .is_none_or(|k| lines[nonblank[k]].trim_end().ends_with([';', '{', '}']));
+        if unclosed == 0 && after_statement {
+            first = i;
+        }
+    }
+    first
+}
+
+// Убирает повторяющиеся пустые строки и пустые строки по краям
+fn collapse(lines: Vec<(usize, &str)>) -> Vec<(usize, &str)> {
+    let mut collapsed: Vec<(usize, &str)> = Vec::new();
+    for (i, line) in lines {
+        let blank = line.trim().is_empty();
+        if blank
+            && collapsed
+                .last()
+                .is_none_or(|(_, last)| last.trim().is_empty())
+        {
+            continue;
+        }
+        collapsed.push((i, line));
+    }
+    while collapsed
+        .last()
+        .is_some_and(|(_, last)| last.trim().is_empty())
+    {
+        collapsed.pop();
+    }
+    collapsed
+}
+
+// Ответ засчитывается целиком или только значением, например `232` вместо `1000 as u8: 232`
+fn is_correct(answer: &str, expected: &str) -> bool {
+    let normalize = |text: &str| text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let answer = normalize(answer);
+    if answer == normalize(expected) {
+        return true;
+    }
+    value_range(expected).is_some_and(|range| !answer.is_empty() && answer == expected[range])
+}
+
+// Варианты ответа: правильный и до трёх искажённых, в случайном порядке.
+// `None`, если правдоподобных искажений не нашлось.
+fn choices(question: &Question, rng: &mut Rng) -> Option<Vec<String>> {
+    let answer = &question.answer;
+    let range = value_range(answer)?;
+    let value = &answer[range.clone()];
+
+    // Сначала значения из кода вопроса, затем арифметические искажения
+    let mut values = literals(&question.context);
+    values.extend(literals(&question.statement));
+    rng.shuffle(&mut values);
+    values.extend(perturb(value));
+
+    let mut choices = vec![answer.clone()];
+    for candidate in values {
+        let choice = format!(
+            "{}{}{}",
+            &answer[..range.start],
+            candidate,
+            &answer[range.end..]
+        );
+        if candidate != value && !choices.contains(&choice) {
+            choices.push(choice);
+        }
+        if choices.len() == 4 {
+            break;
+        }
+    }
+    if choices.len() < 3 {
+        return None;
+    }
+    rng.shuffle(&mut choices);
+    Some(choices)
+}
+
+// Положение значения в строке вывода: последнее число, `()` или `true`/`false`,
+// а если их нет — единственное слово после последнего `: `
+fn value_range(text: &str) -> Option<Range<usize>> {
+    let words = text.split(' ').scan(0, |offset, word| {
+        let start = *offset;
+        *offset += word.len() + 1;
+        Some((start, word))
+    });
+    let mut found = None;
+    for (start, word) in words {
+        let trimmed = word.trim_matches(|c: char| ",;[]{}".contains(c));
+        let trimmed = if trimmed == "()" {
+            trimmed
+        } else {
+            trimmed.trim_matches(|c| c == '(' || c == ')')
+        };
+        let is_value = trimmed == "()"
+            || trimmed == "true"
+            || trimmed == "false"
+            || trimmed.parse::<f64>().is_ok();
+        if is_value && !trimmed.is_empty() {
+            let offset = start + word.find(trimmed).unwrap_or(0);
+            found = Some(offset..offset + trimmed.len());
+        }
+    }
+    found.or_else(|| {
+        let start = text.rfind(": ")? + 2;
+        (!text[start..].contains(' ') && start < text.len()).then_some(start..text.len())
+    })
+}
+
+// Числа и строки, записанные в коде: `1000`, `-1i8`, `65.4321_f32`, `"abc"`
+fn literals(lines: &[(usize, &str)]) -> Vec<String> {
+    let mut values = Vec::new();
+    for (_, line) in lines {
+        let chars: Vec<char> = line.chars().collect();
+        let mut i = 0;
+        while i < chars.len() {
+            if chars[i] == '"' {
+                let end = (i + 1..chars.len())
+                    .find(|&j| chars[j] == '"')
+                    .unwrap_or(chars.len());
+                let literal: String = chars[i + 1..end].iter().collect();
+                // Строки форматирования ответом быть не могут
+                if !literal.contains('{') && !literal.is_empty() {
+                    values.push(literal);
+                }
+                i = end + 1;
+            } else if chars[i].is_ascii_digit()
+                && (i == 0 || !(chars[i - 1].is_alphanumeric() || chars[i - 1] == '_'))
+            {
+                let negative = i > 0 && chars[i - 1] == '-';
+                let end = (i..chars.len())
+                    .find(|&j| !(chars[j].is_ascii_digit() || chars[j] == '.'))
+                    .unwrap_or(chars.len());
+                let mut literal: String = chars[i..end].iter().collect();
+                literal = literal.trim_end_matches('.').to_string();
+                if negative {
+                    literal.insert(0, '-');
+                }
+                values.push(literal);
+                // Пропускаем суффикс типа: `_f32`, `u8`
+                i = (end..chars.len())
+                    .find(|&j| !(chars[j].is_alphanumeric() || chars[j] == '_'))
+                    .unwrap_or(chars.len());
+            } else {
+                i += 1;
+            }
+        }
+    }
+    values
+}
+
+// Типичные ошибки: соседние числа, знак, переполнение при приведении к `u8` и `i8`
+fn perturb(value: &str) -> Vec<String> {
+    match value {
+        "true" => return vec!["false".to_string()],
+        "false" => return vec!["true".to_string()],
+        "()" => return vec!["0".to_string(), "None".to_string()],
+        _ => {}
+    }
+    if let Ok(n) = value.parse::<i64>() {
+        // На краях диапазона `i64` часть искажений переполняется, их пропускаем
+        return [
+            Some(n as u8 as i64),
+            Some(n as i8 as i64),
+            n.checked_neg(),
+            n.checked_add(1),
+            n.checked_sub(1),
+            n.checked_mul(2),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|n| n.to_string())
+        .collect();
+    }
+    if let Ok(x) = value.parse::<f64>() {
+        return vec![
+            x.trunc().to_string(),
+            (-x).to_string(),
+            (x * 10.0).to_string(),
+            (x + 1.0).to_string(),
+        ];
+    }
+    Vec::new()
+}
+
+// Генератор xorshift: для перемешивания вопросов внешние крейты не нужны
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: Option<u64>) -> Rng {
+        let seed = seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_nanos() as u64)
+        });
+        Rng(seed | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = (self.next() % (i as u64 + 1)) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn perturbs_integers() {
+        assert_eq!(perturb("300"), ["44", "44", "-300", "301", "299", "600"]);
+        assert_eq!(perturb("-1"), ["255", "-1", "1", "0", "-2", "-2"]);
+    }
+
+    // На краях диапазона `i64` нет паники, а переполнившиеся варианты пропущены
+    #[test]
+    fn skips_overflowing_integers() {
+        let max = i64::MAX.to_string();
+        assert_eq!(
+            perturb(&max),
+            [
+                "255",
+                "-1",
+                &(-i64::MAX).to_string(),
+                &(i64::MAX - 1).to_string()
+            ]
+        );
+        let min = i64::MIN.to_string();
+        assert_eq!(perturb(&min), ["0", "0", &(i64::MIN + 1).to_string()]);
+    }
+
+    #[test]
+    fn perturbs_floats() {
+        assert_eq!(perturb("2.5"), ["2", "-2.5", "25", "3.5"]);
+    }
+
+    #[test]
+    fn perturbs_other_values() {
+        assert_eq!(perturb("true"), ["false"]);
+        assert_eq!(perturb("false"), ["true"]);
+        assert_eq!(perturb("()"), ["0", "None"]);
+        assert!(perturb("\"привет\"").is_empty());
+    }
+}
@@ -0,0 +1,198 @@
+// `start --format json`: отчёт о запуске разделов для внешних систем.
+// По отчётам удобно сравнивать вывод книги между версиями Rust,
+// например когда меняется форматирование `f32` в `Display` или `Debug`.
+
+use std::fmt::Write;
+
+use crate::annotations::{self, Change};
+use crate::book::Section;
+use crate::i18n::{self, Lang};
+use crate::runner::{self, Options};
+use crate::verify;
+
+// Печатает отчёт в stdout. Возвращает `false`, если какой-то раздел
+// неожиданно запаниковал или его вывод разошёлся с аннотациями.
+pub fn json(sections: &[&Section], options: &Options) -> bool {
+    let (report, ok) = render(sections, options);
+    print!("{}", report);
+    ok
+}
+
+// Текст отчёта и его итог
+fn render(sections: &[&Section], options: &Options) -> (String, bool) {
+    let mut ok = true;
+    let mut entries = Vec::new();
+
+    for section in sections {
+        let Some(run) = section.run else {
+            continue;
+        };
+        let mut entry = String::from("    {\n");
+        field(&mut entry, "number", &string(section.number));
+        field(&mut entry, "title", &string(section.title()));
+        field(&mut entry, "path", &string(section.path));
+        field(&mut entry, "file", &string(section.file));
+        field(
+            &mut entry,
+            "should_panic",
+            &section.should_panic.to_string(),
+        );
+
+        if section.should_panic && !options.panics {
+            field(&mut entry, "status", &string("skipped"));
+            entry.push_str("      \"stdout\": []\n    }");
+            entries.push(entry);
+            continue;
+        }
+
+        let captured = runner::capture_run(run);
+        // Аннотации записаны по-русски: в английском режиме вывод для сверки — отдельный запуск
+        let annotated = match i18n::lang() {
+            Lang::Ru => captured.output.clone(),
+            Lang::En => i18n::with_lang(Lang::Ru, || runner::capture_run(run)).output,
+        };
+        let status = match &captured.panic {
+            Some(_) => {
+                ok &= section.should_panic;
+                "panicked"
+            }
+            None if !matches_annotations(section, &annotated) => {
+                ok = false;
+                "mismatch"
+            }
+            None => "ok",
+        };
+        field(&mut entry, "status", &string(status));
+        let panic = captured.panic.as_deref().map_or("null".to_string(), string);
+        field(&mut entry, "panic", &panic);
+        field(
+            &mut entry,
+            "duration_ms",
+            &format!("{:.3}", captured.duration.as_secs_f64() * 1000.0),
+        );
+
+        let lines: Vec<String> = captured.output.lines().map(string).collect();
+        if lines.is_empty() {
+            entry.push_str("      \"stdout\": []\n    }");
+        } else {
+            write!(
+                entry,
+                "      \"stdout\": [\n        {}\n      ]\n    }}",
+                lines.join(",\n        ")
+            )
+            .unwrap();
+        }
+        entries.push(entry);
+    }
+
+    let mut report = String::from("{\n");
+    writeln!(
+        report,
+        "  \"version\": {},",
+        string(env!("CARGO_PKG_VERSION"))
+    )
+    .unwrap();
+    writeln!(report, "  \"ok\": {},", ok).unwrap();
+    writeln!(report, "  \"sections\": [\n{}\n  ]", entries.join(",\n")).unwrap();
+    report.push_str("}\n");
+    (report, ok)
+}
+
+// Разделы без аннотаций считаются совпавшими: сравнивать их не с чем.
+// Сравнение то же, что у `start verify`
+fn matches_annotations(section: &Section, output: &str) -> bool {
+    let expected = annotations::expected(section);
+    expected.is_empty()
+        || verify::compare(&expected, output)
+            .1
+            .iter()
+            .all(|change| matches!(change, Change::Same(..)))
+}
+
+fn field(entry: &mut String, name: &str, value: &str) {
+    writeln!(entry, "      \"{}\": {},", name, value).unwrap();
+}
+
+// Строка JSON в кавычках
+fn string(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len() + 2);
+    escaped.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(escaped, "\\u{:04x}", c as u32).unwrap(),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::output::Output;
+
+    #[test]
+    fn escapes_json_strings() {
+        assert_eq!(string(r#"он сказал "да""#), r#""он сказал \"да\"""#);
+        assert_eq!(string(r"C:\book"), r#""C:\\book""#);
+        assert_eq!(string("а\nб\tв\r"), r#""а\nб\tв\r""#);
+        assert_eq!(string("\u{1}\u{1f}"), r#""\u0001\u001f""#);
+        // Кириллица и прочий не-ASCII текст остаётся как есть: JSON — это UTF-8
+        assert_eq!(string("Привет, мир ✓"), r#""Привет, мир ✓""#);
+    }
+
+    fn section(out: &Output) {
+        writeln!(out, "Привет, \"мир\"  ");
+        writeln!(out, "второй");
+    }
+
+    #[test]
+    fn renders_a_full_report() {
+        let section = Section {
+            number: "1.1",
+            title: "Привет",
+            path: "start::report::tests::section",
+            file: "src/report.rs",
+            // Пробелы в конце строки вывода не сравниваются, как и в `start verify`
+            source: "fn section(out: &Output) {\n    // >> Привет, \"мир\"\n    // >> второй\n}\n",
+            run: Some(section),
+            should_panic: false,
+        };
+        let (report, ok) = render(&[&section], &Options::default());
+        assert!(ok);
+
+        let (duration, rest): (Vec<&str>, Vec<&str>) = report
+            .lines()
+            .partition(|line| line.contains("\"duration_ms\""));
+        assert_eq!(duration.len(), 1);
+        let expected = format!(
+            r#"{{
+  "version": "{}",
+  "ok": true,
+  "sections": [
+    {{
+      "number": "1.1",
+      "title": "Привет",
+      "path": "start::report::tests::section",
+      "file": "src/report.rs",
+      "should_panic": false,
+      "status": "ok",
+      "panic": null,
+      "stdout": [
+        "Привет, \"мир\"  ",
+        "второй"
+      ]
+    }}
+  ]
+}}"#,
+            env!("CARGO_PKG_VERSION")
+        );
+        assert_eq!(rest.join("\n"), expected);
+    }
+}
@@ -0,0 +1,312 @@
+// Последовательный запуск выбранных разделов.
+// Каждый раздел выполняется под `catch_unwind`: паника в одном разделе
+// (например, сработавший `assert_eq!`) не прерывает остальные.
+
+use std::cell::{Cell, RefCell};
+use std::io::{self, Write};
+use std::panic::{self, AssertUnwindSafe, PanicHookInfo};
+use std::sync::Once;
+use std::time::{Duration, Instant};
+
+use crate::alloc::{self, Scope, Stats};
+use crate::book::Section;
+use crate::i18n;
+use crate::output::Output;
+use crate::progress::Progress;
+use crate::tr;
+use crate::trace;
+
+#[derive(Default)]
+pub struct Options {
+    // Запускать разделы, которые намеренно завершаются паникой
+    pub panics: bool,
+    // Вместо вывода разделов напечатать отчёт в JSON (`--format json`)
+    pub json: bool,
+    // Печатать время выполнения каждого раздела (`--time`)
+    pub time: bool,
+    // Печатать выделения памяти каждого раздела (`--alloc`)
+    pub alloc: bool,
+}
+
+// Чем закончился запуск раздела
+pub enum Status {
+    Passed,
+    Panicked,
+    // Раздел намеренно паникует, а `--panics` не указан
+    Skipped,
+}
+
+// Возвращает `false`, если какой-то раздел неожиданно завершился паникой.
+// Результат каждого раздела записывается в `progress`.
+// Закрытый канал (`start | head`) — обычное завершение, а не ошибка.
+pub fn run(
+    sections: &[&Section],
+    options: &Options,
+    progress: &mut Progress,
+) -> Result<bool, String> {
+    match write_sections(&mut io::stdout().lock(), sections, options, progress) {
+        Ok(ok) => Ok(ok),
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(true),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+fn write_sections(
+    stdout: &mut impl Write,
+    sections: &[&Section],
+    options: &Options,
+    progress: &mut Progress,
+) -> io::Result<bool> {
+    let (mut passed, mut panicked, mut skipped) = (0, 0, 0);
+    let mut ok = true;
+    let mut total = Duration::ZERO;
+
+    for section in sections {
+        writeln!(stdout, "=== {} {} ===", section.number, section.title())?;
+        let result = run_section(section, stdout, options)?;
+        if let Some((Status::Passed | Status::Panicked, elapsed)) = result {
+            if options.time {
+                total += elapsed;
+                writeln!(stdout, "{}", tr!(RUN_TIME, format_duration(elapsed)))?;
+            }
+        }
+        match result {
+            None => {}
+            Some((Status::Passed, _)) => passed += 1,
+            Some((Status::Panicked, _)) => {
+                panicked += 1;
+                ok &= section.should_panic;
+            }
+            Some((Status::Skipped, _)) => skipped += 1,
+        }
+        if let Some((status, _)) = result {
+            status.record(section, progress);
+        }
+        writeln!(stdout)?;
+    }
+
+    writeln!(stdout, "{}", tr!(RUN_TOTAL, passed, panicked, skipped))?;
+    if options.time {
+        writeln!(stdout, "{}", tr!(RUN_TOTAL_TIME, format_duration(total)))?;
+    }
+    Ok(ok)
+}
+
+impl Status {
+    // Ожидаемая паника считается успешным прохождением раздела
+    pub fn record(&self, section: &Section, progress: &mut Progress) {
+        match self {
+            Status::Passed => progress.record_section(section.number, true),
+            Status::Panicked => progress.record_section(section.number, section.should_panic),
+            Status::Skipped => {}
+        }
+    }
+}
+
+// Запускает раздел, записывая его вывод и сообщение о панике в `sink`.
+// Возвращает итог и время выполнения самого раздела, без перевода его вывода
+// и печати хроники; для раздела-заголовка — `None`.
+pub fn run_section(
+    section: &Section,
+    sink: &mut dyn Write,
+    options: &Options,
+) -> io::Result<Option<(Status, Duration)>> {
+    let Some(run) = section.run else {
+        return Ok(None);
+    };
+    if section.should_panic && !options.panics {
+        writeln!(sink, "{}", tr!(RUN_SKIPPED))?;
+        return Ok(Some((Status::Skipped, Duration::ZERO)));
+    }
+
+    let stats = Cell::new(Stats::default());
+    trace::start();
+    let out = Output::new(&mut *sink);
+    let start = Instant::now();
+    let result = isolated(|| {
+        let _scope = options.alloc.then(|| Scope::new(&stats));
+        run(&out);
+    });
+    let elapsed = start.elapsed();
+    // Английский вывод переводится здесь, когда замеры уже закончены
+    drop(out);
+    let status = match result {
+        Ok(()) => Status::Passed,
+        Err(message) => {
+            writeln!(
+                sink,
+                "{}",
+                tr!(RUN_PANICKED, section.number, section.title(), message)
+            )?;
+            Status::Panicked
+        }
+    };
+    trace::finish(|events| print_timeline(sink, events))?;
+    if options.alloc {
+        print_stats(sink, stats.get())?;
+    }
+    Ok(Some((status, elapsed)))
+}
+
+// Хроника владения для разделов, где значения обёрнуты в `Traced`
+fn print_timeline(sink: &mut dyn Write, events: &[String]) -> io::Result<()> {
+    if events.is_empty() {
+        return Ok(());
+    }
+    writeln!(sink, "{}", tr!(RUN_OWNERSHIP))?;
+    for (i, event) in events.iter().enumerate() {
+        writeln!(sink, "{:>4}. {}", i + 1, event)?;
+    }
+    Ok(())
+}
+
+fn print_stats(sink: &mut dyn Write, stats: Stats) -> io::Result<()> {
+    if !alloc::is_installed() {
+        return writeln!(sink, "{}", tr!(RUN_MEMORY_UNAVAILABLE));
+    }
+    let memory = tr!(
+        RUN_MEMORY,
+        stats.allocs,
+        stats.deallocs,
+        stats.reallocs,
+        stats.peak
+    );
+    writeln!(sink, "{}", memory)
+}
+
+// Вывод одного раздела, записанный в буфер.
+// Паника раздела не прерывает проверку остальных и возвращается как ошибка.
+pub fn capture(section: &Section) -> Result<String, String> {
+    match section.run {
+        Some(run) => capture_fn(run),
+        None => Ok(String::new()),
+    }
+}
+
+// То же для произвольной функции, например проверки упражнения
+pub fn capture_fn(run: fn(&Output)) -> Result<String, String> {
+    let captured = capture_run(run);
+    match captured.panic {
+        Some(message) => Err(message),
+        None => Ok(captured.output),
+    }
+}
+
+// Всё, что известно о запуске: вывод (в том числе до паники), паника и время выполнения
+pub struct Captured {
+    pub output: String,
+    pub panic: Option<String>,
+    pub duration: Duration,
+}
+
+pub fn capture_run(run: fn(&Output)) -> Captured {
+    let mut buffer = Vec::new();
+    let out = Output::new(&mut buffer);
+    let start = Instant::now();
+    let result = isolated(|| run(&out));
+    let duration = start.elapsed();
+    drop(out);
+    Captured {
+        duration,
+        output: String::from_utf8_lossy(&buffer).into_owned(),
+        panic: result.err(),
+    }
+}
+
+// Длительность в удобных единицах: `850 нс`, `12.4 мкс`, `3.07 мс`, `1.25 с`
+// (по-английски `850 ns`, `12.4 µs` и так далее)
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos() as f64;
+    let (value, unit) = if nanos < 1e3 {
+        (nanos, tr!(UNIT_NANOS))
+    } else if nanos < 1e6 {
+        (nanos / 1e3, tr!(UNIT_MICROS))
+    } else if nanos < 1e9 {
+        (nanos / 1e6, tr!(UNIT_MILLIS))
+    } else {
+        (nanos / 1e9, tr!(UNIT_SECS))
+    };
+    // Три значащие цифры; наносекунды всегда целые
+    let precision = if nanos < 1e3 {
+        0
+    } else if value < 10.0 {
+        2
+    } else if value < 100.0 {
+        1
+    } else {
+        0
+    };
+    format!("{:.*} {}", precision, value, unit)
+}
+
+thread_local! {
+    // Поток сейчас выполняет `f` внутри `isolated`
+    static ISOLATED: Cell<bool> = const { Cell::new(false) };
+    // Сообщение последней паники внутри `isolated`
+    static PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+// Устанавливает обработчик паники, который внутри `isolated` запоминает сообщение
+// вместо печати в stderr посреди вывода разделов, а вне его печатает как обычно.
+// Вызывается при запуске программы; повторные вызовы ничего не делают.
+pub fn install_panic_hook() {
+    static INSTALLED: Once = Once::new();
+    INSTALLED.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if ISOLATED.get() {
+                PANIC.set(Some(describe(info)));
+            } else {
+                default_hook(info);
+            }
+        }));
+    });
+}
+
+// Выполняет `f`, перехватывая панику
+pub fn isolated<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    // Например, в тестах, где `main` не вызывался
+    install_panic_hook();
+    PANIC.take();
+    let outer = ISOLATED.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    ISOLATED.set(outer);
+
+    result.map_err(|_| PANIC.take().unwrap_or_else(|| tr!(RUN_PANIC).to_string()))
+}
+
+// Сообщение паники вместе с местом в исходнике: `Этот вызов никогда не вернёт управление. (src/func.rs:678)`.
+// Сообщения `panic!` из примеров переводятся так же, как их вывод.
+fn describe(info: &PanicHookInfo) -> String {
+    let payload = info.payload();
+    let message = match payload.downcast_ref::<&str>() {
+        Some(message) => message.to_string(),
+        None => payload
+            .downcast_ref::<String>()
+            .cloned()
+            .unwrap_or_else(|| tr!(RUN_PANIC_NO_MESSAGE).to_string()),
+    };
+    let message = i18n::output(&message);
+    match info.location() {
+        Some(location) => format!("{} ({}:{})", message, location.file(), location.line()),
+        None => message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_durations_with_three_significant_digits() {
+        assert_eq!(format_duration(Duration::ZERO), "0 нс");
+        assert_eq!(format_duration(Duration::from_nanos(999)), "999 нс");
+        assert_eq!(format_duration(Duration::from_nanos(1_000)), "1.00 мкс");
+        assert_eq!(format_duration(Duration::from_nanos(12_345)), "12.3 мкс");
+        assert_eq!(format_duration(Duration::from_micros(999)), "999 мкс");
+        assert_eq!(format_duration(Duration::from_millis(1)), "1.00 мс");
+        assert_eq!(format_duration(Duration::from_micros(123_456)), "123 мс");
+        assert_eq!(format_duration(Duration::from_secs(1)), "1.00 с");
+        assert_eq!(format_duration(Duration::from_secs(75)), "75.0 с");
+    }
+}
@@ -0,0 +1,217 @@
+// Временный крейт с кодом главы во временном каталоге: в нём `start edit` собирает
+// изменённый код раздела, а `start errors` — примеры ошибок компиляции, не трогая исходники книги.
+// Крейт зависит от `start` по пути, поэтому `Output`, `Traced` и остальное доступны как есть,
+// а общий каталог сборки позволяет скомпилировать библиотеку `start` только один раз.
+// Имя пакета у каждого крейта своё, поэтому их программы в общем каталоге не затирают друг друга.
+
+use std::env;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::PathBuf;
+use std::process::Command;
+
+use crate::annotations;
+use crate::book::Section;
+use crate::i18n::{self, Lang};
+use crate::tr;
+
+pub struct Scratch {
+    pub dir: PathBuf,
+    // Имя пакета и программы: `scratch-edit-5_1`
+    name: String,
+}
+
+// Чем закончилась сборка: сообщения компилятора нужны и при успехе, там могут быть предупреждения
+pub struct Build {
+    pub success: bool,
+    pub stderr: String,
+}
+
+// Чем закончился запуск собранной программы
+pub struct Run {
+    pub success: bool,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+// Свой каталог у каждого пользователя: временный каталог бывает общим, а правки
+// из `start edit` должны сохраняться между запусками, поэтому каталог не зависит от процесса
+fn root() -> PathBuf {
+    let user = env::var("USER")
+        .or_else(|_| env::var("USERNAME"))
+        .unwrap_or_default();
+    let user: String = user
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        .collect();
+    if user.is_empty() {
+        return env::temp_dir().join("start-scratch");
+    }
+    env::temp_dir().join(format!("start-scratch-{}", user))
+}
+
+impl Scratch {
+    // `name` — имя каталога крейта, например `edit-5.1`
+    pub fn create(name: &str) -> Result<Scratch, String> {
+        let dir = root().join(name);
+        let name: String = name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let name = format!("scratch-{}", name);
+        let src = dir.join("src");
+        fs::create_dir_all(&src).map_err(|err| format!("{}: {}", src.display(), err))?;
+
+        // Пустая секция `[workspace]`: крейт не должен считаться частью чужого workspace
+        let manifest = format!(
+            "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n\
+             [dependencies]\nstart = {{ path = '{}' }}\n\n[workspace]\n",
+            name,
+            env!("CARGO_MANIFEST_DIR")
+        );
+        let path = dir.join("Cargo.toml");
+        fs::write(&path, manifest).map_err(|err| format!("{}: {}", path.display(), err))?;
+        Ok(Scratch { dir, name })
+    }
+
+    pub fn main_rs(&self) -> PathBuf {
+        self.dir.join("src").join("main.rs")
+    }
+
+    pub fn write(&self, program: &str) -> Result<(), String> {
+        let path = self.main_rs();
+        fs::write(&path, program).map_err(|err| format!("{}: {}", path.display(), err))
+    }
+
+    pub fn build(&self) -> Result<Build, String> {
+        self.compile("build")
+    }
+
+    // Только проверка без генерации кода: для ошибок компиляции этого достаточно
+    pub fn check(&self) -> Result<Build, String> {
+        self.compile("check")
+    }
+
+    fn compile(&self, subcommand: &str) -> Result<Build, String> {
+        let color = if io::stdout().is_terminal() {
+            "always"
+        } else {
+            "never"
+        };
+        let output = self
+            .cargo()
+            .args([subcommand, "--color", color])
+            .output()
+            .map_err(|err| tr!(WATCH_NO_CARGO, err))?;
+        Ok(Build {
+            success: output.status.success(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        })
+    }
+
+    pub fn run(&self) -> Result<Run, String> {
+        let exe = root().join("target").join("debug").join(format!(
+            "{}{}",
+            self.name,
+            env::consts::EXE_SUFFIX
+        ));
+        let output = Command::new(&exe)
+            .output()
+            .map_err(|err| tr!(WATCH_RUN_FAILED, exe.display(), err))?;
+        Ok(Run {
+            success: output.status.success(),
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        })
+    }
+
+    // Для зависимости вне своего workspace cargo передаёт компилятору абсолютные пути,
+    // и `file!()` в `section!` перестал бы совпадать с путём `src/...` из сборки книги.
+    // Префикс пути к книге убирается, чтобы `include_str!` в реестре находил главы.
+    // Флаги из `RUSTFLAGS` пользователя сохраняются, префикс добавляется к ним.
+    fn cargo(&self) -> Command {
+        let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+        let mut rustflags = env::var("RUSTFLAGS").unwrap_or_default();
+        if !rustflags.trim().is_empty() {
+            rustflags.push(' ');
+        }
+        rustflags.push_str(&format!(
+            "--remap-path-prefix={}/=",
+            env!("CARGO_MANIFEST_DIR")
+        ));
+        let mut command = Command::new(cargo);
+        command
+            .arg("--quiet")
+            .current_dir(&self.dir)
+            .env("CARGO_TARGET_DIR", root().join("target"))
+            .env("RUSTFLAGS", rustflags);
+        command
+    }
+}
+
+// Глава раздела как отдельная программа: `crate::` заменён на `start::`,
+// регистр разделов в конце файла убран (его макросы снаружи недоступны),
+// а `main` вызывает функцию раздела на текущем языке
+pub fn program(section: &Section) -> String {
+    let source = match section.source.find("pub const SECTIONS") {
+        Some(end) => &section.source[..end],
+        None => section.source,
+    };
+    let lang = match i18n::lang() {
+        Lang::Ru => "Ru",
+        Lang::En => "En",
+    };
+    format!(
+        "// Копия {file} для раздела {number}: функция `{func}` запускается из `main` в конце файла.\n\
+         // Остальные функции главы оставлены, чтобы код собирался так же, как в книге.\n\
+         #![allow(dead_code, unused_imports)]\n\n\
+         {body}\
+         fn main() {{\n    \
+             start::i18n::set_lang(start::i18n::Lang::{lang});\n    \
+             let mut stdout = std::io::stdout();\n    \
+             {func}(&start::output::Output::new(&mut stdout));\n\
+         }}\n",
+        file = section.file,
+        number = section.number,
+        func = section.func_name(),
+        body = source.replace("crate::", "start::"),
+        lang = lang
+    )
+}
+
+// Строка (с единицы), на которой в программе начинается функция раздела
+pub fn fn_line(program: &str, section: &Section) -> usize {
+    annotations::fn_span(program, section.func_name()).map_or(1, |span| span.start + 1)
+}
+
+#[cfg(all(test, feature = "ch-formatting"))]
+mod tests {
+    use super::*;
+    use crate::book;
+
+    #[test]
+    fn program_calls_the_section_from_main() {
+        let section = book::sections()
+            .into_iter()
+            .find(|section| section.number == "1.1")
+            .unwrap();
+        let program = program(section);
+        assert!(!program.contains("crate::"));
+        assert!(!program.contains("pub const SECTIONS"));
+        assert!(program.contains("use start::output::Output;"));
+        assert!(program.ends_with("    comments(&start::output::Output::new(&mut stdout));\n}\n"));
+
+        let line = fn_line(&program, section);
+        assert!(program
+            .lines()
+            .nth(line - 1)
+            .unwrap()
+            .contains("fn comments("));
+    }
+}
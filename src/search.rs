@@ -0,0 +1,218 @@
+// `start search <запрос>`: поиск по заголовкам, теории и коду разделов.
+// Регистр не учитывается, в том числе для кириллицы, а `ё` совпадает с `е`.
+
+use std::io::{self, IsTerminal};
+use std::ops::Range;
+
+use crate::annotations;
+use crate::book::Section;
+use crate::tr;
+
+// Сколько совпавших строк показывать для одного раздела
+const SNIPPETS: usize = 3;
+// Сколько символов контекста оставлять вокруг совпадения в длинной строке
+const CONTEXT: usize = 40;
+
+// Возвращает `false`, если ничего не найдено
+pub fn search(sections: &[&Section], query: &str) -> bool {
+    let needle: Vec<char> = fold(&query.split_whitespace().collect::<Vec<_>>().join(" "));
+    let highlight = io::stdout().is_terminal();
+    let mut found = 0;
+
+    for section in sections {
+        let mut hits = Vec::new();
+
+        let title = format!("{} {}", section.number, section.title());
+        let title_match = find(&title, &needle);
+        for line in section.theory().lines() {
+            if let Some(range) = find(line, &needle) {
+                hits.push((
+                    tr!(SEARCH_THEORY).to_string(),
+                    snippet(line, range, highlight),
+                ));
+            }
+        }
+        if let Some(span) = annotations::fn_span(section.source, section.func_name()) {
+            for (i, line) in section
+                .source
+                .lines()
+                .enumerate()
+                .skip(span.start)
+                .take(span.len())
+            {
+                if let Some(range) = find(line, &needle) {
+                    let place = format!("{}:{}", section.file, i + 1);
+                    hits.push((place, snippet(line, range, highlight)));
+                }
+            }
+        }
+        if title_match.is_none() && hits.is_empty() {
+            continue;
+        }
+
+        found += 1;
+        let title = match title_match {
+            Some(range) => mark(&title, range, highlight),
+            None => title,
+        };
+        if section.func().is_empty() {
+            println!("{}", title);
+        } else {
+            println!("{}  ({})", title, section.func());
+        }
+        for (place, text) in hits.iter().take(SNIPPETS) {
+            println!("    {}  {}", place, text);
+        }
+        if hits.len() > SNIPPETS {
+            println!("    {}", tr!(SEARCH_MORE, hits.len() - SNIPPETS));
+        }
+    }
+
+    if found == 0 {
+        println!("{}", tr!(SEARCH_NOTHING, query));
+        return false;
+    }
+    println!();
+    println!("{}", tr!(SEARCH_FOUND, found));
+    true
+}
+
+// Подходит ли раздел под запрос: фильтр дерева разделов в `start tui`
+pub fn matches(section: &Section, query: &str) -> bool {
+    let needle = fold(query.trim());
+    let title = format!("{} {}", section.number, section.title());
+    let in_source = annotations::fn_span(section.source, section.func_name()).is_some_and(|span| {
+        section
+            .source
+            .lines()
+            .skip(span.start)
+            .take(span.len())
+            .any(|line| find(line, &needle).is_some())
+    });
+    find(&title, &needle).is_some()
+        || section
+            .theory()
+            .lines()
+            .any(|line| find(line, &needle).is_some())
+        || in_source
+}
+
+// Символ для сравнения без учёта регистра: `Ё` и `ё` приводятся к `е`
+fn fold_char(c: char) -> char {
+    match c.to_lowercase().next().unwrap_or(c) {
+        'ё' => 'е',
+        c => c,
+    }
+}
+
+fn fold(text: &str) -> Vec<char> {
+    text.chars().map(fold_char).collect()
+}
+
+// Байтовый диапазон первого вхождения `needle` в `line`
+fn find(line: &str, needle: &[char]) -> Option<Range<usize>> {
+    if needle.is_empty() {
+        return None;
+    }
+    let chars: Vec<(usize, char)> = line.char_indices().collect();
+    let start = (0..chars.len()).find(|&i| {
+        chars.len() - i >= needle.len()
+            && needle
+                .iter()
+                .zip(&chars[i..])
+                .all(|(&n, &(_, c))| fold_char(c) == n)
+    })?;
+    let end = chars
+        .get(start + needle.len())
+        .map_or(line.len(), |&(offset, _)| offset);
+    Some(chars[start].0..end)
+}
+
+// Строка с выделенным совпадением, обрезанная вокруг него, если она слишком длинная
+fn snippet(line: &str, range: Range<usize>, highlight: bool) -> String {
+    let indent = line.len() - line.trim_start().len();
+    let (line, range) = (&line[indent..], range.start - indent..range.end - indent);
+
+    let before: Vec<(usize, char)> = line[..range.start].char_indices().collect();
+    let after: Vec<(usize, char)> = line[range.end..].char_indices().collect();
+    let (mut prefix, mut suffix) = ("", "");
+    let start = if before.len() > CONTEXT {
+        prefix = "…";
+        before[before.len() - CONTEXT].0
+    } else {
+        0
+    };
+    let end = if after.len() > CONTEXT {
+        suffix = "…";
+        range.end + after[CONTEXT].0
+    } else {
+        line.len()
+    };
+
+    let range = range.start - start..range.end - start;
+    format!(
+        "{}{}{}",
+        prefix,
+        mark(line[start..end].trim_end(), range, highlight),
+        suffix
+    )
+}
+
+// Выделяет совпадение: в терминале — цветом, в файле или конвейере — скобками
+fn mark(text: &str, range: Range<usize>, highlight: bool) -> String {
+    let (open, close) = if highlight {
+        ("\x1b[1;33m", "\x1b[0m")
+    } else {
+        ("[", "]")
+    };
+    let end = range.end.min(text.len());
+    format!(
+        "{}{}{}{}{}",
+        &text[..range.start],
+        open,
+        &text[range.start..end],
+        close,
+        &text[end..]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn found<'a>(line: &'a str, query: &str) -> Option<&'a str> {
+        find(line, &fold(query)).map(|range| &line[range])
+    }
+
+    #[test]
+    fn ignores_case_of_cyrillic() {
+        assert_eq!(found("9.2 Замыкания", "замыкания"), Some("Замыкания"));
+        assert_eq!(found("fn Main()", "MAIN"), Some("Main"));
+        assert_eq!(found("Замыкания", "функции"), None);
+    }
+
+    #[test]
+    fn yo_matches_ye() {
+        assert_eq!(found("ЁЛКА", "елка"), Some("ЁЛКА"));
+        assert_eq!(found("Ещё один", "еще"), Some("Ещё"));
+        assert_eq!(found("Еще один", "ещё"), Some("Еще"));
+    }
+
+    // Контекст отсчитывается в символах, поэтому обрезка не попадает внутрь символа
+    #[test]
+    fn cuts_long_lines_on_char_boundaries() {
+        let line = format!("    {}цель{}", "а".repeat(100), "б".repeat(100));
+        let range = find(&line, &fold("ЦЕЛЬ")).unwrap();
+        assert_eq!(
+            snippet(&line, range, false),
+            format!("…{}[цель]{}…", "а".repeat(CONTEXT), "б".repeat(CONTEXT))
+        );
+    }
+
+    #[test]
+    fn short_lines_are_not_cut() {
+        let line = "    let x = 5; // пять";
+        let range = find(line, &fold("Пять")).unwrap();
+        assert_eq!(snippet(line, range, false), "let x = 5; // [пять]");
+    }
+}
@@ -0,0 +1,309 @@
+// `start serve --port 8080`: книга в браузере без mdBook и без зависимостей.
+// Минимальный HTTP/1.1-сервер на 127.0.0.1 отдаёт оглавление и страницы разделов
+// с теорией, кодом и выводом. Разделы выполняются в этом же процессе, поэтому
+// запросы обрабатываются по одному.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::time::Duration;
+
+use crate::book::Section;
+use crate::export::{escape, html_page, source};
+use crate::runner::{self, Captured};
+use crate::tr;
+
+// Клиент, который открыл соединение и ничего не прислал, не должен держать сервер
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub fn serve(sections: &[&'static Section], port: u16) -> Result<(), String> {
+    let address = (Ipv4Addr::LOCALHOST, port);
+    let listener = TcpListener::bind(address)
+        .map_err(|err| tr!(SERVE_BIND, format!("127.0.0.1:{}", port), err))?;
+    println!("{}", tr!(SERVE_LISTENING, format!("127.0.0.1:{}", port)));
+
+    let mut server = Server {
+        sections,
+        runs: HashMap::new(),
+    };
+    for stream in listener.incoming() {
+        let result = stream.and_then(|stream| server.handle(stream));
+        if let Err(err) = result {
+            eprintln!("{}", tr!(ERROR, err));
+        }
+    }
+    Ok(())
+}
+
+struct Server<'a> {
+    sections: &'a [&'static Section],
+    // Последний запуск каждого открытого раздела; кнопка «Запустить» его обновляет
+    runs: HashMap<&'static str, Captured>,
+}
+
+struct Response {
+    status: &'static str,
+    location: Option<String>,
+    body: String,
+}
+
+impl Response {
+    fn page(status: &'static str, body: String) -> Response {
+        Response {
+            status,
+            location: None,
+            body,
+        }
+    }
+
+    fn not_found(path: &str) -> Response {
+        let message = tr!(SERVE_NOT_FOUND, path);
+        let body = format!(
+            "<p><a href=\"/\">{}</a></p>\n<p>{}</p>\n",
+            tr!(EXPORT_CONTENTS),
+            escape(&message)
+        );
+        Response::page("404 Not Found", html_page(&message, &body))
+    }
+
+    fn write_to(&self, mut stream: &TcpStream) -> io::Result<()> {
+        let mut head = format!(
+            "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\n\
+             Content-Length: {}\r\nConnection: close\r\n",
+            self.status,
+            self.body.len()
+        );
+        if let Some(location) = &self.location {
+            write!(head, "Location: {}\r\n", location).unwrap();
+        }
+        head.push_str("\r\n");
+        stream.write_all(head.as_bytes())?;
+        stream.write_all(self.body.as_bytes())?;
+        stream.flush()
+    }
+}
+
+impl Server<'_> {
+    fn handle(&mut self, stream: TcpStream) -> io::Result<()> {
+        stream.set_read_timeout(Some(READ_TIMEOUT))?;
+        let mut reader = BufReader::new(&stream);
+
+        // Строка запроса `GET /section/9.2 HTTP/1.1`, затем заголовки до пустой строки
+        let mut request = String::new();
+        reader.read_line(&mut request)?;
+        let mut parts = request.split_whitespace();
+        let method = parts.next().unwrap_or_default().to_string();
+        let path = parts.next().unwrap_or_default().to_string();
+
+        let mut length = 0;
+        loop {
+            let mut header = String::new();
+            if reader.read_line(&mut header)? == 0 || header.trim_end().is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    length = value.trim().parse().unwrap_or(0);
+                }
+            }
+        }
+        // Тело запроса (у кнопки «Запустить» оно пустое) читается, чтобы не оборвать соединение
+        io::copy(&mut reader.take(length), &mut io::sink())?;
+
+        let response = self.respond(&method, &path);
+        println!("{} {} — {}", method, path, response.status);
+        response.write_to(&stream)
+    }
+
+    fn respond(&mut self, method: &str, path: &str) -> Response {
+        let route = path.split('?').next().unwrap_or_default();
+        let section = route.strip_prefix("/section/");
+        match method {
+            "GET" if route == "/" => Response::page("200 OK", self.index()),
+            "GET" => match section.and_then(|number| self.find(number)) {
+                Some(section) => Response::page("200 OK", self.section(section)),
+                None => Response::not_found(path),
+            },
+            "POST" => {
+                let number = section.and_then(|rest| rest.strip_suffix("/run"));
+                match number.and_then(|number| self.find(number)) {
+                    Some(section) => {
+                        self.runs.remove(section.number);
+                        // После запуска браузер переходит на страницу раздела,
+                        // и обновление страницы не запускает раздел ещё раз
+                        Response {
+                            status: "303 See Other",
+                            location: Some(format!("/section/{}", section.number)),
+                            body: String::new(),
+                        }
+                    }
+                    None => Response::not_found(path),
+                }
+            }
+            _ => {
+                let message = tr!(SERVE_METHOD, method);
+                Response::page(
+                    "405 Method Not Allowed",
+                    html_page(&message, &escape(&message)),
+                )
+            }
+        }
+    }
+
+    fn find(&self, number: &str) -> Option<&'static Section> {
+        self.sections
+            .iter()
+            .find(|section| section.number == number)
+            .copied()
+    }
+
+    fn index(&self) -> String {
+        let mut body = format!("<h1>{}</h1>\n<ul class=\"toc\">\n", tr!(EXPORT_CONTENTS));
+        for section in self.sections {
+            writeln!(
+                body,
+                "<li style=\"margin-left: {}rem\"><a href=\"/section/{1}\">{1} {2}</a></li>",
+                (section.depth() - 1) * 2,
+                section.number,
+                escape(section.title())
+            )
+            .unwrap();
+        }
+        body.push_str("</ul>\n");
+        html_page(tr!(EXPORT_CONTENTS), &body)
+    }
+
+    fn section(&mut self, section: &'static Section) -> String {
+        let position = self
+            .sections
+            .iter()
+            .position(|other| other.number == section.number)
+            .unwrap_or_default();
+        let mut body = format!("<p><a href=\"/\">{}</a>", tr!(EXPORT_CONTENTS));
+        if let Some(prev) = position.checked_sub(1).map(|i| self.sections[i]) {
+            write!(body, " · <a href=\"/section/{0}\">← {0}</a>", prev.number).unwrap();
+        }
+        if let Some(next) = self.sections.get(position + 1) {
+            write!(body, " · <a href=\"/section/{0}\">{0} →</a>", next.number).unwrap();
+        }
+        body.push_str("</p>\n");
+        writeln!(
+            body,
+            "<h1>{} {}</h1>",
+            section.number,
+            escape(section.title())
+        )
+        .unwrap();
+
+        let theory = section.theory();
+        if !theory.is_empty() {
+            writeln!(body, "<div class=\"theory\">{}</div>", escape(&theory)).unwrap();
+        }
+        if let Some(source) = source(section) {
+            writeln!(body, "<pre><code>{}</code></pre>", escape(&source)).unwrap();
+        }
+
+        if let Some(run) = section.run {
+            writeln!(
+                body,
+                "<form method=\"post\" action=\"/section/{}/run\"><button>{}</button></form>",
+                section.number,
+                tr!(SERVE_RUN)
+            )
+            .unwrap();
+            let captured = self
+                .runs
+                .entry(section.number)
+                .or_insert_with(|| runner::capture_run(run));
+            let time = runner::format_duration(captured.duration);
+            writeln!(body, "<p>{}</p>", tr!(SERVE_RUN_TIME, time)).unwrap();
+            if !captured.output.is_empty() {
+                writeln!(
+                    body,
+                    "<p>{}</p>\n<pre class=\"output\">{}</pre>",
+                    tr!(EXPORT_OUTPUT),
+                    escape(&captured.output)
+                )
+                .unwrap();
+            }
+            if let Some(panic) = &captured.panic {
+                writeln!(
+                    body,
+                    "<p>{}</p>\n<pre class=\"panic\">{}</pre>",
+                    tr!(EXPORT_PANIC),
+                    escape(panic)
+                )
+                .unwrap();
+            }
+        }
+        html_page(&format!("{} {}", section.number, section.title()), &body)
+    }
+}
+
+// Тесты открывают страницы раздела 1.1
+#[cfg(all(test, feature = "ch-formatting"))]
+mod tests {
+    use super::*;
+    use std::net::SocketAddr;
+    use std::thread;
+
+    use crate::book;
+
+    fn request(address: SocketAddr, request: &str) -> String {
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    // Сервер на свободном порту, который обслуживает `requests` соединений и завершается
+    #[test]
+    fn serves_pages_and_runs_sections() {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let address = listener.local_addr().unwrap();
+        let requests = 5;
+        let server = thread::spawn(move || {
+            let sections = book::sections();
+            let mut server = Server {
+                sections: &sections,
+                runs: HashMap::new(),
+            };
+            for stream in listener.incoming().take(requests) {
+                server.handle(stream.unwrap()).unwrap();
+            }
+        });
+
+        let index = request(address, "GET / HTTP/1.1\r\nHost: localhost\r\n\r\n");
+        assert!(index.starts_with("HTTP/1.1 200 OK\r\n"), "{}", index);
+        assert!(index.contains("<a href=\"/section/1.1\">1.1 "));
+
+        let page = request(address, "GET /section/1.1 HTTP/1.1\r\n\r\n");
+        assert!(page.starts_with("HTTP/1.1 200 OK\r\n"), "{}", page);
+        assert!(page.contains("<pre class=\"output\">Is 'x' 10 or 100? x = 10"));
+
+        let run = request(
+            address,
+            "POST /section/1.1/run HTTP/1.1\r\nContent-Length: 0\r\n\r\n",
+        );
+        assert!(run.starts_with("HTTP/1.1 303 See Other\r\n"), "{}", run);
+        assert!(run.contains("\r\nLocation: /section/1.1\r\n"));
+
+        let missing = request(address, "GET /section/99 HTTP/1.1\r\n\r\n");
+        assert!(
+            missing.starts_with("HTTP/1.1 404 Not Found\r\n"),
+            "{}",
+            missing
+        );
+
+        let method = request(address, "DELETE / HTTP/1.1\r\n\r\n");
+        assert!(
+            method.starts_with("HTTP/1.1 405 Method Not Allowed\r\n"),
+            "{}",
+            method
+        );
+
+        server.join().unwrap();
+    }
+}
@@ -0,0 +1,150 @@
+// Теория раздела: комментарии `// ...` и `/* ... */` между заголовком раздела
+// (`// 5.1 Приведение типов`) и его функцией. Комментарии внутри кода
+// (в `impl`, телах функций) считаются пояснениями к коду и не попадают в теорию.
+
+use crate::annotations;
+use crate::book::{self, Section};
+
+pub fn theory(section: &Section) -> String {
+    let lines: Vec<&str> = section.source.lines().collect();
+    let Some(header) = lines
+        .iter()
+        .position(|line| header_number(line) == Some(section.number))
+    else {
+        return String::new();
+    };
+
+    // Теория заканчивается на функции раздела или на заголовке следующего раздела
+    let numbers: Vec<&str> = book::sections()
+        .iter()
+        .filter(|other| other.file == section.file)
+        .map(|other| other.number)
+        .collect();
+    let signature = format!("fn {}(", section.func_name());
+    let end = (header + 1..lines.len())
+        .find(|&i| {
+            let line = lines[i].strip_prefix("pub ").unwrap_or(lines[i]);
+            line.starts_with(&signature)
+                || header_number(lines[i]).is_some_and(|number| numbers.contains(&number))
+        })
+        .unwrap_or(lines.len());
+
+    let mut paragraphs: Vec<String> = Vec::new();
+    let mut i = header + 1;
+    while i < end {
+        let line = lines[i];
+        if line.starts_with("///") {
+            // Документация к типам и функциям библиотеки относится к коду, а не к теории
+        } else if let Some(text) = line.strip_prefix("//") {
+            paragraphs.push(
+                text.strip_prefix(' ')
+                    .unwrap_or(text)
+                    .trim_end()
+                    .to_string(),
+            );
+        } else if line.starts_with("/*") {
+            let block_end = annotations::block_end(&lines, i).min(end - 1);
+            paragraphs.extend(block_text(&lines[i..=block_end]));
+            i = block_end;
+        } else if line.trim().is_empty() {
+            paragraphs.push(String::new());
+        } else if !paragraphs.last().is_some_and(String::is_empty) {
+            // Код между комментариями разделяет абзацы
+            paragraphs.push(String::new());
+        }
+        i += 1;
+    }
+
+    // Схлопываем повторяющиеся пустые строки и убираем их по краям
+    let mut text: Vec<String> = Vec::new();
+    for line in paragraphs {
+        if !(line.is_empty() && text.last().is_none_or(String::is_empty)) {
+            text.push(line);
+        }
+    }
+    while text.last().is_some_and(String::is_empty) {
+        text.pop();
+    }
+    text.join("\n")
+}
+
+// Строки блочного комментария без `/*`, `*/` и общего отступа
+fn block_text(block: &[&str]) -> Vec<String> {
+    let last = block.len() - 1;
+    let contents: Vec<&str> = block
+        .iter()
+        .enumerate()
+        .map(|(j, line)| {
+            let mut content = line.trim_end();
+            if j == 0 {
+                content = content.trim_start().trim_start_matches("/*");
+            }
+            if j == last {
+                content = content.trim_end_matches("*/");
+            }
+            content.trim_end()
+        })
+        .collect();
+
+    let indent = contents
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    contents
+        .iter()
+        .map(|line| line.get(indent..).unwrap_or(line.trim_start()).to_string())
+        .collect()
+}
+
+// Номер раздела в заголовке вида `// 9.2.1 Захват`
+fn header_number(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix("//")?.trim_start();
+    let number = rest.split_whitespace().next()?.trim_end_matches('.');
+    let is_number = number.starts_with(|c: char| c.is_ascii_digit())
+        && number.chars().all(|c| c.is_ascii_digit() || c == '.');
+    is_number.then_some(number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(any(feature = "ch-flow-control", feature = "ch-functions"))]
+    fn theory_of(number: &str) -> String {
+        let section = book::sections()
+            .into_iter()
+            .find(|section| section.number == number)
+            .unwrap();
+        theory(section)
+    }
+
+    #[test]
+    fn parses_header_numbers() {
+        assert_eq!(header_number("// 9.2.1 Захват"), Some("9.2.1"));
+        assert_eq!(header_number("// 7. Выражение"), Some("7"));
+        assert_eq!(header_number("// Обычный комментарий"), None);
+        assert_eq!(header_number("    // 9.2.1 внутри функции"), None);
+    }
+
+    #[cfg(feature = "ch-functions")]
+    #[test]
+    fn collects_comments_between_header_and_function() {
+        let theory = theory_of("9.2.4");
+        assert!(theory.starts_with("Так как замыкания могут использоваться в аргументах"));
+        assert!(theory.contains("Объявляем функцию, которая принимает обобщённый тип `F`"));
+        // Комментарии в телах функций — пояснения к коду, а не теория
+        assert!(!theory.contains("Определяем замыкание"));
+        assert!(!theory.contains("*/"));
+    }
+
+    // У раздела-заголовка нет функции, его теория заканчивается на следующем заголовке
+    #[cfg(feature = "ch-flow-control")]
+    #[test]
+    fn heading_theory_ends_at_the_next_header() {
+        let theory = theory_of("8.5.1");
+        assert!(theory.starts_with("Блок match может деструктурировать элементы"));
+        assert!(theory.ends_with("    Кортежи"));
+    }
+}
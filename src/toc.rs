@@ -0,0 +1,110 @@
+// Оглавление книги с отступами по уровню вложенности и функцией каждого раздела.
+// Слева — отметка прогресса: ✓ пройден, ✗ упал или задание не решено.
+
+use crate::book::Section;
+use crate::progress::Progress;
+
+pub fn print(sections: &[&Section], progress: &Progress) {
+    for line in lines(sections, progress) {
+        println!("{}", line);
+    }
+}
+
+// Строки оглавления: отметка прогресса, заголовок с отступом и, если у раздела есть код, его функция
+fn lines(sections: &[&Section], progress: &Progress) -> Vec<String> {
+    let lines: Vec<(String, &str)> = sections
+        .iter()
+        .map(|section| {
+            let indent = "  ".repeat(section.depth() - 1);
+            let title = format!(
+                "{} {}{} {}",
+                progress.mark(section),
+                indent,
+                section.number,
+                section.title()
+            );
+            (title, section.func())
+        })
+        .collect();
+
+    // Выравниваем колонку с функциями по самому длинному заголовку
+    let width = lines
+        .iter()
+        .map(|(title, _)| title.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    lines
+        .into_iter()
+        .map(|(title, func)| {
+            if func.is_empty() {
+                title
+            } else {
+                let padding = width - title.chars().count();
+                format!("{}{}  {}", title, " ".repeat(padding), func)
+            }
+        })
+        .collect()
+}
+
+// Тесты строят оглавление по разделам главы 8
+#[cfg(all(test, feature = "ch-flow-control"))]
+mod tests {
+    use super::*;
+    use crate::book::{self, SectionNumber, Selection};
+
+    fn toc(numbers: &[&str], progress: &Progress) -> Vec<String> {
+        let selection = Selection {
+            numbers: numbers
+                .iter()
+                .map(|number| SectionNumber::parse(number).unwrap())
+                .collect(),
+            ..Selection::default()
+        };
+        lines(&selection.select(book::sections()), progress)
+    }
+
+    #[test]
+    fn shows_only_selected_sections() {
+        let progress = Progress::default();
+        let lines = toc(&["8.5"], &progress);
+        assert_eq!(lines.len(), 8);
+        assert!(lines
+            .iter()
+            .all(|line| line.trim_start().starts_with("8.5")));
+        assert!(!toc(&["8.5", "9.1"], &progress)
+            .iter()
+            .any(|line| line.contains("8.6")));
+    }
+
+    #[test]
+    fn indents_by_depth_and_aligns_functions() {
+        let lines = toc(&["8.5"], &Progress::default());
+        assert!(lines[0].starts_with("    8.5 Match "));
+        assert!(lines[2].starts_with("        8.5.1.1 Кортежи "));
+        // Раздел-заголовок без функции
+        assert_eq!(lines[1], "      8.5.1 Деструктуризация");
+
+        // Колонка считается в символах: заголовки кириллические
+        let columns: Vec<usize> = lines
+            .iter()
+            .filter_map(|line| {
+                let byte = line.find("loop_control::")?;
+                Some(line[..byte].chars().count())
+            })
+            .collect();
+        assert_eq!(columns.len(), 7);
+        assert!(columns.iter().all(|&column| column == columns[0]));
+    }
+
+    #[test]
+    fn marks_progress() {
+        let mut progress = Progress::default();
+        progress.record_section("8.5.1.1", true);
+        progress.record_section("8.5.1.2", false);
+        let lines = toc(&["8.5.1"], &progress);
+        assert!(lines[0].starts_with("      8.5.1 "));
+        assert!(lines[1].starts_with("✓       8.5.1.1 "));
+        assert!(lines[2].starts_with("✗       8.5.1.2 "));
+    }
+}
@@ -0,0 +1,163 @@
+//! Хроника владения для примеров из глав 4 и 9: `Traced<T>` записывает, когда значение
+//! создано, клонировано, перемещено в замыкание и удалено. После вывода раздела `start`
+//! печатает эту хронику, и видно, в какой момент значение перестаёт существовать.
+//!
+//! `Traced<T>` разыменовывается в `T` и печатается так же, как `T`,
+//! поэтому примеры работают с ним как с самим значением.
+//!
+//! Записи хроники — строки, выделенные посреди раздела, поэтому они создаются и удаляются
+//! в `alloc::uncounted` и не искажают замер `--alloc`.
+
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+
+use crate::alloc;
+use crate::tr;
+
+thread_local! {
+    // События текущего раздела; `None`, пока хроника не ведётся (например, в `start verify`)
+    static EVENTS: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+    static NEXT_ID: Cell<usize> = const { Cell::new(1) };
+}
+
+/// Значение с именем, которое записывает в хронику своё создание, клонирование и удаление.
+pub struct Traced<T: fmt::Debug> {
+    name: &'static str,
+    // Номер экземпляра: у клона он свой, чтобы отличать его от оригинала
+    id: usize,
+    value: T,
+}
+
+impl<T: fmt::Debug> Traced<T> {
+    /// Оборачивает `value` и записывает его создание под именем `name`.
+    pub fn new(name: &'static str, value: T) -> Traced<T> {
+        let traced = Traced {
+            name,
+            id: NEXT_ID.replace(NEXT_ID.get() + 1),
+            value,
+        };
+        record(tr!(TRACE_CREATED), || traced.describe());
+        traced
+    }
+
+    /// Перемещение само по себе ничего не выполняет, поэтому о нём сообщают явно,
+    /// прямо перед замыканием, которое захватит значение.
+    pub fn moved_into(&self, closure: &str) {
+        record(tr!(TRACE_MOVED), || {
+            tr!(TRACE_MOVED_INTO, self.label(), closure)
+        });
+    }
+
+    fn label(&self) -> String {
+        format!("#{} `{}`", self.id, self.name)
+    }
+
+    fn describe(&self) -> String {
+        format!("{} = {:?}", self.label(), self.value)
+    }
+}
+
+impl<T: fmt::Debug + Clone> Clone for Traced<T> {
+    fn clone(&self) -> Traced<T> {
+        let clone = Traced {
+            name: self.name,
+            id: NEXT_ID.replace(NEXT_ID.get() + 1),
+            value: self.value.clone(),
+        };
+        record(tr!(TRACE_CLONED), || {
+            format!("{} -> #{}", self.label(), clone.id)
+        });
+        clone
+    }
+}
+
+impl<T: fmt::Debug> Drop for Traced<T> {
+    fn drop(&mut self) {
+        record(tr!(TRACE_DROPPED), || self.describe());
+    }
+}
+
+impl<T: fmt::Debug> Deref for Traced<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: fmt::Debug> DerefMut for Traced<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Traced<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.value, f)
+    }
+}
+
+impl<T: fmt::Debug + fmt::Display> fmt::Display for Traced<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.value, f)
+    }
+}
+
+fn record(action: &str, details: impl FnOnce() -> String) {
+    alloc::uncounted(|| {
+        EVENTS.with_borrow_mut(|events| {
+            if let Some(events) = events {
+                events.push(format!("{:<10} {}", action, details()));
+            }
+        });
+    });
+}
+
+/// Начинает хронику раздела с чистого листа.
+pub fn start() {
+    NEXT_ID.set(1);
+    EVENTS.set(Some(Vec::new()));
+}
+
+/// Заканчивает хронику и передаёт её события по порядку в `f`.
+/// События удаляются сразу после `f`, тоже вне замера.
+pub fn finish<T>(f: impl FnOnce(&[String]) -> T) -> T {
+    alloc::uncounted(|| f(&EVENTS.take().unwrap_or_default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_the_lifetime_of_a_value() {
+        start();
+        {
+            let text = Traced::new("text", String::from("hi"));
+            let copy = text.clone();
+            copy.moved_into("consume");
+            let consume = move || drop(copy);
+            consume();
+            assert_eq!(format!("{} {:?}", text, text), "hi \"hi\"");
+        }
+        let events = finish(|events| events.to_vec());
+        assert_eq!(
+            events,
+            [
+                "создано    #1 `text` = \"hi\"",
+                "клон       #1 `text` -> #2",
+                "перемещено #2 `text` в замыкание `consume`",
+                "удалено    #2 `text` = \"hi\"",
+                "удалено    #1 `text` = \"hi\"",
+            ]
+        );
+    }
+
+    // Вне `start`/`finish` хроника не ведётся
+    #[test]
+    fn records_nothing_outside_a_section() {
+        drop(Traced::new("x", 1));
+        assert!(finish(|events| events.is_empty()));
+    }
+}
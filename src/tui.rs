@@ -0,0 +1,597 @@
+// `start tui`: полноэкранный просмотр книги в терминале.
+// Слева — дерево глав и разделов, справа — теория, код или вывод выбранного раздела.
+// Терминал переключается в «сырой» режим через `stty`, а экран рисуется ANSI-последовательностями,
+// поэтому сторонние библиотеки не нужны; работает в терминалах Linux и macOS.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::io::{self, IsTerminal, Read, Write};
+use std::process::{Command, Stdio};
+
+use crate::book::Section;
+use crate::explain;
+use crate::export;
+use crate::progress::Progress;
+use crate::runner;
+use crate::search;
+use crate::tr;
+
+// Ширина дерева разделов; на узком экране — треть ширины
+const TREE_WIDTH: usize = 40;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Pane {
+    Theory,
+    Source,
+    Output,
+}
+
+const PANES: [Pane; 3] = [Pane::Theory, Pane::Source, Pane::Output];
+
+#[derive(Debug, PartialEq)]
+enum Key {
+    Up,
+    Down,
+    Left,
+    Right,
+    PageUp,
+    PageDown,
+    Home,
+    End,
+    Enter,
+    Tab,
+    BackTab,
+    Backspace,
+    Esc,
+    Interrupt,
+    Char(char),
+}
+
+pub fn tui(sections: &[&'static Section], progress: &mut Progress) -> Result<(), String> {
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        return Err(tr!(TUI_NO_TERMINAL).to_string());
+    }
+    let mut browser = Browser::new(sections, progress);
+    let _terminal = Terminal::raw()?;
+    let mut stdin = io::stdin().lock();
+    let mut buffer = [0; 64];
+    loop {
+        browser.draw();
+        // Escape-последовательность клавиши приходит целиком за одно чтение,
+        // так что одиночный Esc отличается от стрелок без ожидания
+        let len = stdin.read(&mut buffer).map_err(|err| err.to_string())?;
+        if len == 0 {
+            return Ok(());
+        }
+        for key in keys(&buffer[..len]) {
+            if !browser.press(key) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+// Терминал в «сыром» режиме на альтернативном экране; при выходе, в том числе
+// по ошибке, настройки `stty` и основной экран восстанавливаются
+struct Terminal {
+    saved: String,
+}
+
+impl Terminal {
+    fn raw() -> Result<Terminal, String> {
+        let saved = stty(&["-g"])?.trim().to_string();
+        stty(&["raw", "-echo"])?;
+        print!("\x1b[?1049h\x1b[?25l");
+        io::stdout().flush().unwrap();
+        Ok(Terminal { saved })
+    }
+
+    // Строки и столбцы; размер запрашивается перед каждой перерисовкой,
+    // чтобы экран подстраивался под изменённое окно
+    fn size() -> (usize, usize) {
+        let size = stty(&["size"]).unwrap_or_default();
+        let mut numbers = size.split_whitespace().map(|n| n.parse().unwrap_or(0));
+        let rows = numbers.next().filter(|&rows| rows >= 5).unwrap_or(24);
+        let columns = numbers
+            .next()
+            .filter(|&columns| columns >= 40)
+            .unwrap_or(80);
+        (rows, columns)
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        print!("\x1b[?25h\x1b[?1049l");
+        io::stdout().flush().unwrap();
+        let _ = stty(&[&self.saved]);
+    }
+}
+
+fn stty(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .output()
+        .map_err(|err| tr!(TUI_STTY, err))?;
+    if !output.status.success() {
+        let err = String::from_utf8_lossy(&output.stderr);
+        return Err(tr!(TUI_STTY, err.trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn keys(bytes: &[u8]) -> Vec<Key> {
+    let mut keys = Vec::new();
+    let mut rest = bytes;
+    while let Some((&byte, tail)) = rest.split_first() {
+        rest = tail;
+        let key = match byte {
+            0x1b => match rest {
+                [b'[' | b'O', tail @ ..] => {
+                    let end = tail
+                        .iter()
+                        .position(|b| b.is_ascii_alphabetic() || *b == b'~')
+                        .map_or(tail.len(), |i| i + 1);
+                    let (sequence, tail) = tail.split_at(end);
+                    rest = tail;
+                    match sequence {
+                        b"A" => Key::Up,
+                        b"B" => Key::Down,
+                        b"C" => Key::Right,
+                        b"D" => Key::Left,
+                        b"H" | b"1~" => Key::Home,
+                        b"F" | b"4~" => Key::End,
+                        b"5~" => Key::PageUp,
+                        b"6~" => Key::PageDown,
+                        b"Z" => Key::BackTab,
+                        _ => continue,
+                    }
+                }
+                _ => Key::Esc,
+            },
+            0x03 => Key::Interrupt,
+            b'\t' => Key::Tab,
+            b'\r' | b'\n' => Key::Enter,
+            0x7f | 0x08 => Key::Backspace,
+            byte if byte < 0x20 => continue,
+            byte => {
+                // Кириллица в поле поиска приходит несколькими байтами UTF-8
+                let len = match byte {
+                    0xc0..=0xdf => 2,
+                    0xe0..=0xef => 3,
+                    0xf0.. => 4,
+                    _ => 1,
+                };
+                let end = (len - 1).min(rest.len());
+                let (bytes, tail) = rest.split_at(end);
+                rest = tail;
+                let text = [&[byte], bytes].concat();
+                match std::str::from_utf8(&text)
+                    .ok()
+                    .and_then(|s| s.chars().next())
+                {
+                    Some(c) => Key::Char(c),
+                    None => continue,
+                }
+            }
+        };
+        keys.push(key);
+    }
+    keys
+}
+
+struct Browser<'a> {
+    sections: &'a [&'static Section],
+    progress: &'a mut Progress,
+    // Разделы с подразделами, которые сейчас свёрнуты
+    collapsed: HashSet<&'static str>,
+    selected: usize,
+    // Первая видимая строка дерева и правой панели
+    tree_top: usize,
+    scroll: usize,
+    pane: Pane,
+    // Фильтр дерева по запросу и поле ввода, пока запрос набирается
+    query: String,
+    editing: bool,
+    // Вывод разделов, которые уже запускались; `r` запускает раздел заново
+    outputs: HashMap<&'static str, String>,
+}
+
+impl<'a> Browser<'a> {
+    fn new(sections: &'a [&'static Section], progress: &'a mut Progress) -> Browser<'a> {
+        let mut browser = Browser {
+            sections,
+            progress,
+            collapsed: HashSet::new(),
+            selected: 0,
+            tree_top: 0,
+            scroll: 0,
+            pane: Pane::Theory,
+            query: String::new(),
+            editing: false,
+            outputs: HashMap::new(),
+        };
+        // Сначала видны только главы
+        for (i, section) in sections.iter().enumerate() {
+            if browser.has_children(i) {
+                browser.collapsed.insert(section.number);
+            }
+        }
+        browser
+    }
+
+    fn has_children(&self, index: usize) -> bool {
+        let prefix = format!("{}.", self.sections[index].number);
+        self.sections
+            .get(index + 1)
+            .is_some_and(|next| next.number.starts_with(&prefix))
+    }
+
+    fn hidden(&self, section: &Section) -> bool {
+        self.collapsed.iter().any(|number| {
+            section
+                .number
+                .strip_prefix(number)
+                .is_some_and(|rest| rest.starts_with('.'))
+        })
+    }
+
+    // Индексы разделов, которые сейчас видны в дереве
+    fn rows(&self) -> Vec<usize> {
+        let filter = !self.query.trim().is_empty();
+        (0..self.sections.len())
+            .filter(|&i| {
+                let section = self.sections[i];
+                if filter {
+                    search::matches(section, &self.query)
+                } else {
+                    !self.hidden(section)
+                }
+            })
+            .collect()
+    }
+
+    fn select(&mut self, index: usize) {
+        if index != self.selected {
+            self.selected = index;
+            self.scroll = 0;
+        }
+    }
+
+    // Возвращает `false`, когда пора выходить
+    fn press(&mut self, key: Key) -> bool {
+        if self.editing {
+            match key {
+                Key::Interrupt => return false,
+                Key::Enter => self.editing = false,
+                Key::Esc => {
+                    self.editing = false;
+                    self.query.clear();
+                }
+                Key::Backspace => {
+                    self.query.pop();
+                }
+                Key::Char(c) => self.query.push(c),
+                _ => {}
+            }
+            // Выбранный раздел должен остаться среди найденных
+            let rows = self.rows();
+            if !rows.contains(&self.selected) {
+                if let Some(&first) = rows.first() {
+                    self.select(first);
+                }
+            }
+            return true;
+        }
+
+        let rows = self.rows();
+        let row = rows.iter().position(|&i| i == self.selected);
+        let page = Terminal::size().0.saturating_sub(3).max(1);
+        match key {
+            Key::Char('q') | Key::Interrupt => return false,
+            Key::Up | Key::Char('k') => {
+                if let Some(&index) = row
+                    .and_then(|row| row.checked_sub(1))
+                    .and_then(|row| rows.get(row))
+                {
+                    self.select(index);
+                }
+            }
+            Key::Down | Key::Char('j') => {
+                let next = row.map_or(0, |row| row + 1);
+                if let Some(&index) = rows.get(next) {
+                    self.select(index);
+                }
+            }
+            Key::Home => {
+                if let Some(&index) = rows.first() {
+                    self.select(index);
+                }
+            }
+            Key::End => {
+                if let Some(&index) = rows.last() {
+                    self.select(index);
+                }
+            }
+            Key::Right | Key::Char('l') => {
+                let number = self.sections[self.selected].number;
+                if !self.collapsed.remove(number) && self.has_children(self.selected) {
+                    self.select(self.selected + 1);
+                }
+            }
+            Key::Left | Key::Char('h') => {
+                let number = self.sections[self.selected].number;
+                if self.has_children(self.selected) && !self.collapsed.contains(number) {
+                    self.collapsed.insert(number);
+                } else if let Some((parent, _)) = number.rsplit_once('.') {
+                    if let Some(index) = self.sections.iter().position(|s| s.number == parent) {
+                        self.select(index);
+                    }
+                }
+            }
+            Key::Enter => {
+                let number = self.sections[self.selected].number;
+                if self.has_children(self.selected) && !self.collapsed.remove(number) {
+                    self.collapsed.insert(number);
+                } else if !self.has_children(self.selected) {
+                    self.pane = Pane::Output;
+                    self.scroll = 0;
+                }
+            }
+            Key::Tab | Key::BackTab => {
+                let current = PANES
+                    .iter()
+                    .position(|&pane| pane == self.pane)
+                    .unwrap_or(0);
+                let next = match key {
+                    Key::Tab => (current + 1) % PANES.len(),
+                    _ => (current + PANES.len() - 1) % PANES.len(),
+                };
+                self.pane = PANES[next];
+                self.scroll = 0;
+            }
+            Key::Char(c @ '1'..='3') => {
+                self.pane = PANES[c as usize - '1' as usize];
+                self.scroll = 0;
+            }
+            Key::PageDown | Key::Char(' ') => self.scroll += page,
+            Key::PageUp | Key::Char('b') => self.scroll = self.scroll.saturating_sub(page),
+            Key::Char('r') => {
+                self.outputs.remove(self.sections[self.selected].number);
+                self.pane = Pane::Output;
+                self.scroll = 0;
+            }
+            Key::Char('/') => {
+                self.editing = true;
+                self.query.clear();
+            }
+            Key::Esc => self.query.clear(),
+            _ => {}
+        }
+        true
+    }
+
+    // Вывод раздела так же, как в `start interactive`: с паникой и хроникой владения.
+    // Результат запуска отмечается в прогрессе.
+    fn output(&mut self, section: &'static Section) -> &str {
+        let progress = &mut *self.progress;
+        self.outputs.entry(section.number).or_insert_with(|| {
+            let options = runner::Options {
+                panics: true,
+                ..runner::Options::default()
+            };
+            let mut buffer = Vec::new();
+            // Запись в `Vec` не бывает неудачной
+            if let Ok(Some((status, _))) = runner::run_section(section, &mut buffer, &options) {
+                status.record(section, progress);
+                progress.save();
+            }
+            String::from_utf8_lossy(&buffer).into_owned()
+        })
+    }
+
+    // Строки правой панели, уже разбитые по её ширине
+    fn content(&mut self, width: usize) -> Vec<String> {
+        let section = self.sections[self.selected];
+        let text = match self.pane {
+            Pane::Theory => {
+                let theory = section.theory();
+                if theory.is_empty() {
+                    return vec![tr!(EXPLAIN_NO_THEORY).to_string()];
+                }
+                return explain::wrap(&theory, width)
+                    .lines()
+                    .map(str::to_string)
+                    .collect();
+            }
+            Pane::Source => match export::source(section) {
+                Some(source) => source,
+                None => return vec![tr!(TUI_NO_FUNCTION).to_string()],
+            },
+            Pane::Output if section.run.is_none() => {
+                return vec![tr!(TUI_NO_FUNCTION).to_string()];
+            }
+            Pane::Output => self.output(section).to_string(),
+        };
+        // Код и вывод переносятся по символам, чтобы не потерять отступы
+        let mut lines = Vec::new();
+        for line in text.replace('\t', "    ").lines() {
+            let chars: Vec<char> = line.chars().collect();
+            if chars.is_empty() {
+                lines.push(String::new());
+            }
+            for chunk in chars.chunks(width) {
+                lines.push(chunk.iter().collect());
+            }
+        }
+        lines
+    }
+
+    fn draw(&mut self) {
+        let (height, width) = Terminal::size();
+        let tree_width = TREE_WIDTH.min(width / 3);
+        let text_width = width - tree_width - 3;
+        let body = height - 2;
+
+        let rows = self.rows();
+        let row = rows.iter().position(|&i| i == self.selected).unwrap_or(0);
+        if row < self.tree_top {
+            self.tree_top = row;
+        } else if row >= self.tree_top + body {
+            self.tree_top = row + 1 - body;
+        }
+        self.tree_top = self.tree_top.min(rows.len().saturating_sub(body));
+
+        let content = self.content(text_width);
+        self.scroll = self.scroll.min(content.len().saturating_sub(body));
+
+        let mut screen = String::from("\x1b[H");
+        // Первая строка: заголовок дерева и вкладки панелей
+        let mut tabs = String::new();
+        for (i, pane) in PANES.iter().enumerate() {
+            let name = match pane {
+                Pane::Theory => tr!(TUI_THEORY),
+                Pane::Source => tr!(TUI_SOURCE),
+                Pane::Output => tr!(TUI_OUTPUT),
+            };
+            if *pane == self.pane {
+                write!(tabs, "\x1b[7m {} {} \x1b[0m", i + 1, name).unwrap();
+            } else {
+                write!(tabs, " {} {} ", i + 1, name).unwrap();
+            }
+        }
+        let section = self.sections[self.selected];
+        write!(
+            screen,
+            "\x1b[1m{}\x1b[0m │ {}  {} {}\x1b[K\r\n",
+            fit(tr!(EXPORT_CONTENTS), tree_width),
+            tabs,
+            section.number,
+            section.title()
+        )
+        .unwrap();
+
+        for line in 0..body {
+            let tree = match rows.get(self.tree_top + line) {
+                Some(&index) => {
+                    let text = fit(&self.tree_line(index), tree_width);
+                    if index == self.selected {
+                        format!("\x1b[7m{}\x1b[0m", text)
+                    } else {
+                        text
+                    }
+                }
+                None => fit("", tree_width),
+            };
+            let text = content.get(self.scroll + line).map_or("", String::as_str);
+            write!(screen, "{} │ {}\x1b[K\r\n", tree, text).unwrap();
+        }
+
+        // Последняя строка: поле поиска, найденное или подсказка по клавишам
+        let status = if self.editing {
+            tr!(TUI_SEARCH, self.query)
+        } else if !self.query.trim().is_empty() {
+            tr!(TUI_FOUND, self.query, rows.len())
+        } else {
+            tr!(TUI_HELP).to_string()
+        };
+        write!(screen, "\x1b[7m{}\x1b[0m\x1b[K", fit(&status, width)).unwrap();
+        if self.editing {
+            // Курсор в поле поиска
+            let column = status.chars().count().min(width - 1) + 1;
+            write!(screen, "\x1b[{};{}H\x1b[?25h", height, column).unwrap();
+        } else {
+            screen.push_str("\x1b[?25l");
+        }
+
+        let mut stdout = io::stdout().lock();
+        stdout.write_all(screen.as_bytes()).unwrap();
+        stdout.flush().unwrap();
+    }
+
+    fn tree_line(&self, index: usize) -> String {
+        let section = self.sections[index];
+        let marker = if !self.has_children(index) {
+            ' '
+        } else if self.collapsed.contains(section.number) {
+            '▸'
+        } else {
+            '▾'
+        };
+        format!(
+            "{}{}{} {} {}",
+            self.progress.mark(section),
+            "  ".repeat(section.depth() - 1),
+            marker,
+            section.number,
+            section.title()
+        )
+    }
+}
+
+// Строка ровно в `width` символов: обрезанная или дополненная пробелами
+fn fit(text: &str, width: usize) -> String {
+    let mut line: String = text.chars().take(width).collect();
+    let len = line.chars().count();
+    line.extend(std::iter::repeat_n(' ', width - len));
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_arrows_enter_and_letters() {
+        assert_eq!(
+            keys(b"\x1b[A\x1b[B\x1b[C\x1b[D"),
+            [Key::Up, Key::Down, Key::Right, Key::Left]
+        );
+        // Стрелки в режиме приложения терминала
+        assert_eq!(keys(b"\x1bOA\x1bOB"), [Key::Up, Key::Down]);
+        assert_eq!(keys(b"\r\n"), [Key::Enter, Key::Enter]);
+        assert_eq!(keys(b"q\x03"), [Key::Char('q'), Key::Interrupt]);
+        assert_eq!(
+            keys(b"\x1b[5~\x1b[6~\x1b[Z"),
+            [Key::PageUp, Key::PageDown, Key::BackTab]
+        );
+        assert_eq!(keys("ж".as_bytes()), [Key::Char('ж')]);
+        assert_eq!(keys(b"\x1b"), [Key::Esc]);
+        // Незнакомые последовательности пропускаются целиком
+        assert_eq!(keys(b"\x1b[99Xq"), [Key::Char('q')]);
+    }
+
+    // Дерево раздела 8.5: Enter раскрывает раздел, стрелки ходят по видимым строкам, `q` — выход
+    #[cfg(feature = "ch-flow-control")]
+    #[test]
+    fn enter_expands_and_arrows_move() {
+        use crate::book::{self, SectionNumber, Selection};
+
+        let selection = Selection {
+            numbers: vec![SectionNumber::parse("8.5").unwrap()],
+            ..Selection::default()
+        };
+        let sections = selection.select(book::sections());
+        let mut progress = Progress::default();
+        let mut browser = Browser::new(&sections, &mut progress);
+        assert_eq!(browser.rows(), [0]);
+
+        assert!(browser.press(Key::Enter));
+        assert_eq!(browser.sections[browser.rows()[1]].number, "8.5.1");
+        assert!(browser.press(Key::Down));
+        assert_eq!(browser.sections[browser.selected].number, "8.5.1");
+        assert!(browser.press(Key::Right));
+        assert!(browser.press(Key::Down));
+        assert_eq!(browser.sections[browser.selected].number, "8.5.1.1");
+        assert!(browser.press(Key::Up));
+        assert_eq!(browser.sections[browser.selected].number, "8.5.1");
+
+        // Enter на разделе без подразделов открывает его вывод
+        browser.select(2);
+        assert!(browser.press(Key::Enter));
+        assert!(browser.pane == Pane::Output);
+
+        assert!(!browser.press(Key::Char('q')));
+    }
+}
@@ -1,9 +1,13 @@
+//! Глава 5 «Типы»: приведение, суффиксы литералов, вывод типов и псевдонимы.
+
 // Атрибут, который убирает предупреждения компилятора о неиспользуемом коде
-#![allow(dead_code)]
 #![allow(unused_variables)]
 // Строчка ниже убирает все предупреждения, которые вызываются переполнением при преобразовании типов.
 #![allow(overflowing_literals)]
 
+use crate::book::Section;
+use crate::output::Output;
+
 // 5 Типы
 /*
 Rust предоставляет несколько механизмов изменения или определения примитивных и пользовательских типов:
@@ -22,7 +26,9 @@ Rust не предусматривает неявного (принудител
 Поведение всех приведений между встроенными типами чётко определено в Rust.
 */
 
-fn casting() {
+/// Явное приведение `as` между числовыми типами, переполнение и насыщение.
+#[allow(clippy::unnecessary_cast, clippy::cast_nan_to_int)]
+pub fn casting(out: &Output) {
     let decimal = 65.4321_f32;
     // Ошибка! Нет неявного преобразования
     // let integer: u8 = decimal;
@@ -35,7 +41,8 @@ fn casting() {
     // Число с плавающей точкой не может быть напрямую конвертировано в символ.
     // let character = decimal as char;
 
-    println!(
+    writeln!(
+        out,
         "Преобразование: {} -> {} -> {}",
         decimal, integer, character
     );
@@ -44,19 +51,19 @@ fn casting() {
     // Когда преобразовывается любое значение в беззнаковый тип T std::T::MAX + 1 добавляется или вычитается до тех пор, пока значение не будет помещаться в новый тип.
     // 1000 поместится в u16
 
-    println!("1000 as u16: {}", 1000 as u16);
+    writeln!(out, "1000 as u16: {}", 1000 as u16);
     // >> 1000 as u16: 1000
 
     // 1000 - 256 - 256 - 256 = 232 Подробнее. Первые 8 младших битов (LSB) сохраняются, а старшие биты (MSB) будут усечены.
-    println!("1000 as u8: {}", 1000 as u8);
+    writeln!(out, "1000 as u8: {}", 1000 as u8);
     // >> 1000 as u8: 232
 
     // -1 + 256 = 255
-    println!("-1 as u8: {}", -1i8 as u8);
+    writeln!(out, "-1 as u8: {}", -1i8 as u8);
     // >> -1 as u8: 255
 
     // Для положительных чисел результатом будет остаток от деления
-    println!("1000 mod 256: {}", 1000 % 256);
+    writeln!(out, "1000 mod 256: {}", 1000 % 256);
     // >> 1000 mod 256: 232
 
     /*
@@ -64,31 +71,31 @@ fn casting() {
         первое преобразование к соответствующему типу без знака. Если старший бит этого значения равен 1, то это значение — отрицательное.
     */
     // За исключением тех случаев когда значение умещается в тип.
-    println!("128 as i16: {}", 128 as i16);
+    writeln!(out, "128 as i16: {}", 128 as i16);
     // >> 128 as i16: 128
 
     // 128 as u8 -> 128, дополнительный код которого в 8 битах:
-    println!("128 as i8: {}", 128 as i8);
+    writeln!(out, "128 as i8: {}", 128 as i8);
     // >> 128 as i8: -128
 
     // повторяем примеры
     // 1000 as u8 -> 232
-    println!("1000 as u8: {}", 1000 as u8);
+    writeln!(out, "1000 as u8: {}", 1000 as u8);
     // >> 1000 as u8: 232
 
     // и дополнительный код 232 — это -24
-    println!("232 as i8: {}", 232 as i8);
+    writeln!(out, "232 as i8: {}", 232 as i8);
     // >> 232 as i8: -24
 
     /*
         Начиная с Rust 1.45, ключевое слово `as` выполняет *насыщающее приведение* (saturating cast) при преобразовании в целое число с плавающей точкой.
         Если значение числа с плавающей точкой превышает верхнюю границу или меньше нижней границы, то возвращаемое значение будет равняться пересечённой границе.
     */
-    println!("300.0 == {}", 300.0_f32 as u8);
+    writeln!(out, "300.0 == {}", 300.0_f32 as u8);
     // >> 300.0 == 255
-    println!("-100.0 as u8 == {}", -100.0_f32 as u8);
+    writeln!(out, "-100.0 as u8 == {}", -100.0_f32 as u8);
     // >> -100.0 as u8 == 0
-    println!("nan as u8 == {}", f32::NAN as u8);
+    writeln!(out, "nan as u8 == {}", f32::NAN as u8);
     // >> nan as u8 == 0
 
     /*
@@ -97,11 +104,15 @@ fn casting() {
     Используйте эти методы с умом:
     */
     unsafe {
-        println!("300.0 == {}", 300.0_f32.to_int_unchecked::<u8>());
+        writeln!(out, "300.0 == {}", 300.0_f32.to_int_unchecked::<u8>());
         // >> 300.0 == 44
-        println!("-100.0 as u8 == {}", (-100.0_f32).to_int_unchecked::<u8>());
+        writeln!(
+            out,
+            "-100.0 as u8 == {}",
+            (-100.0_f32).to_int_unchecked::<u8>()
+        );
         // >> -100.0 as u8 == 156
-        println!("nan as u8 == {}", f32::NAN.to_int_unchecked::<u8>())
+        writeln!(out, "nan as u8 == {}", f32::NAN.to_int_unchecked::<u8>())
         // >> nan as u8 == 0
     }
 }
@@ -114,7 +125,8 @@ fn casting() {
 Без суффикса тип литерала будет зависеть от того, как он используется.
 Если нет никаких ограничений, то компилятор будет использовать i32 для целочисленных литералов, а f64 — для литералов с плавающей точкой.
 */
-fn literals() {
+/// Суффиксы числовых литералов и размер получившихся типов.
+pub fn literals(out: &Output) {
     // Литералы с суффиксами. Их тип известен при инициализации.
     let x = 1u8;
     let y = 2u32;
@@ -125,15 +137,15 @@ fn literals() {
     let i = 1;
     let f = 1.0;
 
-    println!("size of `x` in bytes: {}", std::mem::size_of_val(&x));
+    writeln!(out, "size of `x` in bytes: {}", std::mem::size_of_val(&x));
     // >> size of `x` in bytes: 1
-    println!("size of `y` in bytes: {}", std::mem::size_of_val(&y));
+    writeln!(out, "size of `y` in bytes: {}", std::mem::size_of_val(&y));
     // >> size of `y` in bytes: 4
-    println!("size of `z` in bytes: {}", std::mem::size_of_val(&z));
+    writeln!(out, "size of `z` in bytes: {}", std::mem::size_of_val(&z));
     // >> size of `z` in bytes: 4
-    println!("size of `i` in bytes: {}", std::mem::size_of_val(&i));
+    writeln!(out, "size of `i` in bytes: {}", std::mem::size_of_val(&i));
     // >> size of `i` in bytes: 4
-    println!("size of `f` in bytes: {}", std::mem::size_of_val(&f));
+    writeln!(out, "size of `f` in bytes: {}", std::mem::size_of_val(&f));
     // >> size of `f` in bytes: 8
 }
 
@@ -144,7 +156,9 @@ fn literals() {
 Вот расширенный пример вывода типов:
 */
 
-fn type_inference() {
+/// Вывод типа вектора по первому добавленному элементу.
+#[allow(clippy::vec_init_then_push)]
+pub fn type_inference(out: &Output) {
     // Благодаря выведению типов компилятор знает, что `elem` имеет тип `u8`.
     let elem = 5u8;
 
@@ -158,7 +172,7 @@ fn type_inference() {
     Ага! Теперь компилятор знает,
     что `vec` — это вектор, который хранит в себе тип `u8` (`Vec<u8>`)
     */
-    println!("{:?}", vec);
+    writeln!(out, "{:?}", vec);
     // >> [5]
 }
 
@@ -177,11 +191,13 @@ type Inch = u64;
 // Используйте этот атрибут, чтобы не выводить предупреждение об именах не в стиле CamelCase
 type u64_t = u64;
 
-fn nicknames() {
+/// Псевдонимы `type` для числовых типов.
+pub fn nicknames(out: &Output) {
     let nanoseconds: NanoSecond = 5 as u64_t;
     let inches: Inch = 2 as u64_t;
 
-    println!(
+    writeln!(
+        out,
         "{} nanoseconds + {} inches = {} unit?",
         nanoseconds,
         inches,
@@ -193,9 +209,20 @@ fn nicknames() {
 Основное применение псевдонимов — сокращение размера кода:
     например, тип IoResult<T> является псевдонимом типа Result<T, IoError>.
 */
-pub fn run5() {
-    casting();
-    literals();
-    type_inference();
-    nicknames();
+
+/// Глава целиком: все разделы подряд, без заголовков.
+pub fn run5(out: &Output) {
+    casting(out);
+    literals(out);
+    type_inference(out);
+    nicknames(out);
 }
+
+/// Разделы главы 5 в порядке книги.
+pub const SECTIONS: &[Section] = &[
+    heading!("5", "Типы"),
+    section!("5.1", "Приведение типов", casting),
+    section!("5.2", "Литералы", literals),
+    section!("5.3", "Вывод типов", type_inference),
+    section!("5.4", "Псевдонимы", nicknames),
+];
@@ -1,6 +1,7 @@
-// Атрибут, который убирает предупреждения компилятора о неиспользуемом коде
-#![allow(dead_code)]
-#![allow(unused_variables)]
+//! Глава 3 «Пользовательские типы»: структуры, перечисления и константы.
+
+use crate::book::Section;
+use crate::output::Output;
 
 // 3 Пользовательские типы
 /*
@@ -19,6 +20,7 @@
     Единичная структура, которая не имеет полей, но может быть полезна для обобщённых типов.
 */
 
+#[allow(dead_code)]
 #[derive(Debug)]
 struct Person {
     name: String,
@@ -31,24 +33,32 @@ struct Unit;
 struct Pair(i32, f32);
 
 // Структура с двумя полями
+/// Точка на плоскости.
 #[derive(Debug)]
-struct Point {
-    x: f32,
-    y: f32,
+pub struct Point {
+    /// Абсцисса.
+    pub x: f32,
+    /// Ордината.
+    pub y: f32,
 }
 
 // Структуры могут быть использованы в качестве полей другой структуры
+/// Прямоугольник, заданный левым верхним и правым нижним углами.
 #[derive(Debug)]
-struct Rectangle {
-    top_left: Point,
-    bottom_right: Point,
+pub struct Rectangle {
+    /// Левый верхний угол.
+    pub top_left: Point,
+    /// Правый нижний угол.
+    pub bottom_right: Point,
 }
 
 impl Rectangle {
-    fn rect_area(&self) -> f32 {
+    /// Площадь прямоугольника.
+    pub fn rect_area(&self) -> f32 {
         (self.top_left.x - self.bottom_right.x) * (self.top_left.y - self.bottom_right.y)
     }
-    fn square(&self, point: Point, width_heidth: f32) -> Rectangle {
+    /// Квадрат со стороной `width_heidth` и левым верхним углом в `point`.
+    pub fn square(&self, point: Point, width_heidth: f32) -> Rectangle {
         let Point { x, y } = point;
         Rectangle {
             top_left: point,
@@ -60,22 +70,27 @@ impl Rectangle {
     }
 }
 
-fn _struct() {
+/// Кортежные, классические и единичные структуры, обновление и деструктуризация.
+pub fn structures(out: &Output) {
     let name = String::from("Peter");
     let age = 27;
     // Ключи структуры можно не писать если наименование переменой соответствует ключу структуры
     let peter = Person { name, age };
-    println!("{:?}", peter);
+    writeln!(out, "{:?}", peter);
     // >> Person { name: "Peter", age: 27 }
 
     let point = Point { x: 10.3, y: 0.4 };
-    println!("Координаты точки: ({}, {})", point.x, point.y);
+    writeln!(out, "Координаты точки: ({}, {})", point.x, point.y);
     // >> Координаты точки: (10.3, 0.4)
 
     // Создадим новую точку, используя синтаксис обновления структуры и нашу существующую точку
     // `bottom_right.y` будет тем же самым, что и `point.y`, так как мы взяли это поле из `point`
     let bottom_right = Point { x: 5.2, ..point };
-    println!("Вторая точка: ({}, {})", bottom_right.x, bottom_right.y);
+    writeln!(
+        out,
+        "Вторая точка: ({}, {})",
+        bottom_right.x, bottom_right.y
+    );
     // >> Вторая точка: (5.2, 0.4)
 
     // Деструктурируем структуру при помощи `let`
@@ -89,7 +104,7 @@ fn _struct() {
             x: letf_edge,
             y: top_edge,
         },
-        bottom_right: bottom_right,
+        bottom_right,
     };
 
     // Создадим unit-структуру
@@ -98,18 +113,18 @@ fn _struct() {
     // Создадим кортежную структуру
     let pair = Pair(1, 0.1);
     // Доступ к полям кортежной структуры
-    println!("Pair cодержит {:?} и {:?}", pair.0, pair.1);
+    writeln!(out, "Pair cодержит {:?} и {:?}", pair.0, pair.1);
     // >> Pair cодержит 1 и 0.1
 
     // Деструктурируем кортежную структуру
     let Pair(integer, decimal) = pair;
-    println!("Pair содержит {:?} и {:?}", integer, decimal);
+    writeln!(out, "Pair содержит {:?} и {:?}", integer, decimal);
     // >> Pair содержит 1 и 0.1
 
-    println!("Площадь прямоугольника: {}", _rectangle.rect_area());
+    writeln!(out, "Площадь прямоугольника: {}", _rectangle.rect_area());
     // >> Площадь прямоугольника: 0
-    println!("Квадрат: {:?}", _rectangle.square(point, 13.1));
-    // >> Квадрат: Rectangle { top_left: Point { x: 10.3, y: 0.4 }, bottom_right: Point { x: 23.400002, y: 13.5 }
+    writeln!(out, "Квадрат: {:?}", _rectangle.square(point, 13.1));
+    // >> Квадрат: Rectangle { top_left: Point { x: 10.3, y: 0.4 }, bottom_right: Point { x: 23.400002, y: 13.5 } }
 }
 
 // 3.2 Перечисления
@@ -135,19 +150,20 @@ enum WebEvent {
     Click { x: i64, y: i64 },
 }
 
-fn inspect(event: WebEvent) {
+fn inspect(out: &Output, event: WebEvent) {
     match event {
-        WebEvent::PageLoad => println!("Страница загружена"),
-        WebEvent::PageUnload => println!("Стараница не загружена"),
-        WebEvent::KeyPress(c) => println!("Нажата '{}'.", c),
-        WebEvent::Paste(s) => println!("Нажата \"{}\"", s),
+        WebEvent::PageLoad => writeln!(out, "Страница загружена"),
+        WebEvent::PageUnload => writeln!(out, "Стараница не загружена"),
+        WebEvent::KeyPress(c) => writeln!(out, "Нажата '{}'.", c),
+        WebEvent::Paste(s) => writeln!(out, "Нажата \"{}\"", s),
         WebEvent::Click { x, y } => {
-            println!("Кликнуто на x={}, y={}", x, y);
+            writeln!(out, "Кликнуто на x={}, y={}", x, y);
         }
     }
 }
 
-fn _enum() {
+/// Перечисление событий веб-страницы и разбор его вариантов через `match`.
+pub fn enums(out: &Output) {
     let pressed = WebEvent::KeyPress('x');
     // `to_owned()` создаст `String` из строкового среза.
     let pasted = WebEvent::Paste("Мой текст".to_owned());
@@ -155,15 +171,15 @@ fn _enum() {
     let load = WebEvent::PageLoad;
     let unload = WebEvent::PageUnload;
 
-    inspect(pressed);
+    inspect(out, pressed);
     // >> Нажата 'x'.
-    inspect(pasted);
+    inspect(out, pasted);
     // >> Нажата "Мой текст"
-    inspect(click);
+    inspect(out, click);
     // >> Кликнуто на x=20, y=80
-    inspect(load);
+    inspect(out, load);
     // >> Страница загружена
-    inspect(unload);
+    inspect(out, unload);
     // >> Стараница не загружена
 }
 // 3.2.1 Псевдонимы типов
@@ -171,6 +187,7 @@ fn _enum() {
 Если вы используете псевдонимы типов, то вы можете обратиться к каждому варианту перечисления через его псевдоним.
 Это может быть полезно, если у перечисления слишком длинное имя или оно слишком обобщено, и вы хотите переименовать его.
 */
+#[allow(dead_code)]
 #[derive(Debug)]
 enum VeriVerboseEnumOfThingsDoWithNumber {
     Add,
@@ -188,13 +205,14 @@ impl VeriVerboseEnumOfThingsDoWithNumber {
     }
 }
 
-fn pseudonym() {
+/// Псевдоним типа для длинного имени перечисления и `Self` внутри `impl`.
+pub fn pseudonym(out: &Output) {
     // Мы можем обратиться к каждому варианту перечисления через его
     // псевдоним, а не через его длинное неудобное имя.
     let x = Operations::Add;
-    println!("x: {:?}", x);
+    writeln!(out, "x: {:?}", x);
     // >> x: Add
-    println!("x.run: {}", x.run(10, 20));
+    writeln!(out, "x.run: {}", x.run(10, 20));
     // >> x.run: 30
 }
 
@@ -202,16 +220,19 @@ fn pseudonym() {
 
 // Декларация use используется, чтобы убрать необходимость указывать область видимости
 
+#[allow(dead_code)]
 enum Status {
     Rich,
     Poor,
 }
+#[allow(dead_code)]
 enum Work {
     Civilian,
     Solidier,
 }
 
-fn use_of_declaration() {
+/// `use` для вариантов перечисления: обращение к ним без имени типа.
+pub fn use_of_declaration(out: &Output) {
     // Используем `use` для каждого из вариантов, чтобы они были доступны без указания области видимости.
     use Status::{Poor, Rich};
     // Автоматически используем `use` для каждого из вариантов в `Work`.
@@ -224,21 +245,22 @@ fn use_of_declaration() {
     let work = Civilian;
 
     match status {
-        Rich => println!("У богатого куча денег!"),
-        Poor => println!("У бедняка денег нет, но он держится..."),
+        Rich => writeln!(out, "У богатого куча денег!"),
+        Poor => writeln!(out, "У бедняка денег нет, но он держится..."),
     }
     // >> У бедняка денег нет, но он держится...
 
     match work {
-        Civilian => println!("Гражданин работате!"),
-        Solidier => println!("Солдыты служат!"),
+        Civilian => writeln!(out, "Гражданин работате!"),
+        Solidier => writeln!(out, "Солдыты служат!"),
     }
     // >> Гражданин работате!
 }
 
-// 3.2.2 С-подобные перечисления
+// 3.2.2.1 С-подобные перечисления
 // enum могут быть использованы как С-подобные перечисления.
 // enum с неявным дискриминатором (начинается с 0)
+#[allow(dead_code)]
 enum Number {
     Zero,
     One,
@@ -246,22 +268,27 @@ enum Number {
     Ten,
 }
 // enum с явным дискриминатором
-enum Color {
+/// Цвет, дискриминант которого равен его коду RGB: `Color::Red as i32 == 0xff0000`.
+pub enum Color {
+    /// Красный, `0xff0000`.
     Red = 0xff0000,
+    /// Зелёный, `0x00ff00`.
     Green = 0x00ff00,
+    /// Синий, `0x0000ff`.
     Blue = 0x0000ff,
 }
 
-fn c_like() {
+/// Перечисления в стиле C: неявные и явные дискриминанты и приведение к `i32`.
+pub fn c_like(out: &Output) {
     // `enums` может быть преобразован в целочисленное значение. (идекс значения от 0)
-    println!("Нулевой элемент {}", Number::Zero as i32);
+    writeln!(out, "Нулевой элемент {}", Number::Zero as i32);
     // >> Нулевой элемент 0
-    println!("Первый элемент {}", Number::One as i32);
+    writeln!(out, "Первый элемент {}", Number::One as i32);
     // >> Первый элемент 1
 
-    println!("Красный цвет #{:06x}", Color::Red as i32);
+    writeln!(out, "Красный цвет #{:06x}", Color::Red as i32);
     // >> Красный цвет #ff0000
-    println!("Голубой цвет #{:06x}", Color::Blue as i32);
+    writeln!(out, "Голубой цвет #{:06x}", Color::Blue as i32);
     // >> Голубой цвет #0000ff
 }
 
@@ -269,29 +296,35 @@ fn c_like() {
 // Импорт всего модуля
 use List::*;
 
-enum List {
+/// Связанный список чисел `u32`.
+pub enum List {
     // Cons: Кортежная структура, которая хранит элемент и указатель на следующий узел
+    /// Узел с элементом и остатком списка.
     Cons(u32, Box<List>),
     // Nil: Узел, обозначающий конец связанного списка
+    /// Конец списка.
     Nil,
 }
 
 // Методы могут быть присоединены к перечислению
 impl List {
     // Создаём пустой список
-    fn new() -> List {
+    /// Пустой список.
+    pub fn new() -> List {
         // `Nil` имеет тип `List`
         Nil
     }
 
     // Функция, которая принимает список и возвращает тот же список, но с новым элементом в начале
-    fn prepende(self, elem: u32) -> List {
+    /// Список с `elem` в начале.
+    pub fn prepende(self, elem: u32) -> List {
         // `Cons` также имеет тип `List`
         Cons(elem, Box::new(self))
     }
 
     // Возвращаем длину списка
-    fn len(&self) -> u32 {
+    /// Длина списка.
+    pub fn len(&self) -> u32 {
         /*
         `self` должен быть сопоставлен (проверен на соответствие), поскольку поведение этого метода зависит от варианта `self`
         `self` имеет тип `&List`, а `*self` имеет тип `List`, сопоставление на конкретном типе `T` предпочтительнее, чем сопоставление по ссылке `&T`
@@ -304,19 +337,34 @@ impl List {
         }
     }
 
+    // Пустой ли список: `len` для этого пришлось бы пройти его целиком
+    /// Пустой ли список.
+    pub fn is_empty(&self) -> bool {
+        matches!(*self, Nil)
+    }
+
     // Возвращаем представление списка в виде (размещённой в куче) строки
-    fn stringify(&self) -> String {
+    /// Список строкой, например `3, 2, 1, Nil`.
+    pub fn stringify(&self) -> String {
         match *self {
             Cons(head, ref tail) => {
                 // `format!` похож на `print!`, но возвращает строку размещённую в куче, вместо вывода на консоль
                 format!("{}, {}", head, tail.stringify())
             }
-            Nil => format!("Nil"),
+            Nil => "Nil".to_string(),
         }
     }
 }
 
-fn example_list() {
+// `List::default()` — тот же пустой список, что и `List::new()`
+impl Default for List {
+    fn default() -> List {
+        List::new()
+    }
+}
+
+/// Связанный список на перечислении с `Box`.
+pub fn example_list(out: &Output) {
     // Создаём пустой связанный список
     let mut list = List::new();
 
@@ -325,9 +373,9 @@ fn example_list() {
     list = list.prepende(2);
     list = list.prepende(3);
 
-    println!("Размер связаного списка {}", list.len());
+    writeln!(out, "Размер связаного списка {}", list.len());
     // >> Размер связаного списка 3
-    println!("{}", list.stringify());
+    writeln!(out, "{}", list.stringify());
     // >> 3, 2, 1, Nil
 }
 
@@ -348,15 +396,17 @@ fn is_big(n: i32) -> bool {
     n > THERESHOLD
 }
 
-fn constants() {
+/// Константы `const` и статические переменные `static`.
+pub fn constants(out: &Output) {
     let n = 16;
 
     // Получаем доступ к константе внутри функции main
-    println!("Это язык {}", LANGUAGE);
+    writeln!(out, "Это язык {}", LANGUAGE);
     // >> Это язык Rust
-    println!("Установим предел равный {}", THERESHOLD);
+    writeln!(out, "Установим предел равный {}", THERESHOLD);
     // >> Установим предел равный 10
-    println!(
+    writeln!(
+        out,
         "Число {} {} предела",
         n,
         if is_big(n) {
@@ -368,12 +418,25 @@ fn constants() {
     // >> Число 16 Больше предела
 }
 
-pub fn run3() {
-    _struct();
-    _enum();
-    pseudonym();
-    use_of_declaration();
-    c_like();
-    example_list();
-    constants();
+/// Глава целиком: все разделы подряд, без заголовков.
+pub fn run3(out: &Output) {
+    structures(out);
+    enums(out);
+    pseudonym(out);
+    use_of_declaration(out);
+    c_like(out);
+    example_list(out);
+    constants(out);
 }
+
+/// Разделы главы 3 в порядке книги.
+pub const SECTIONS: &[Section] = &[
+    heading!("3", "Пользовательские типы"),
+    section!("3.1", "Структуры", structures),
+    section!("3.2", "Перечисления", enums),
+    section!("3.2.1", "Псевдонимы типов", pseudonym),
+    section!("3.2.2", "Декларация use", use_of_declaration),
+    section!("3.2.2.1", "С-подобные перечисления", c_like),
+    section!("3.2.3", "Пример: Связанный список", example_list),
+    section!("3.3", "Константы", constants),
+];
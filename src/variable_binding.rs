@@ -1,7 +1,12 @@
+//! Глава 4 «Связывание переменных»: изменяемость, область видимости, затенение и заморозка.
+
 // Атрибут, который убирает предупреждения компилятора о неиспользуемом коде
-#![allow(dead_code)]
 #![allow(unused_variables)]
 
+use crate::book::Section;
+use crate::output::Output;
+use crate::trace::Traced;
+
 // 4 Связывание переменных
 
 /*
@@ -11,7 +16,8 @@ Rust предоставляет безопасность типов с помо
 Значения (как и литералы) могут быть привязаны к переменным, используя оператор let.
 */
 
-fn binding_variable() {
+/// Связывание значений с переменными через `let`, подавление предупреждения о неиспользуемой.
+pub fn binding_variable(out: &Output) {
     let an_integer = 1u32;
     let a_boolean = true;
     let unit = ();
@@ -19,11 +25,11 @@ fn binding_variable() {
     // Копируем `an_integer` в `copied_integer`
     let copied_integer = an_integer;
 
-    println!("An integer: {:?}", copied_integer);
+    writeln!(out, "An integer: {:?}", copied_integer);
     // >> An integer: 1
-    println!("A boolean: {:?}", a_boolean);
+    writeln!(out, "A boolean: {:?}", a_boolean);
     // >> A boolean: true
-    println!("Meet the unnit value: {:?}", unit);
+    writeln!(out, "Meet the unnit value: {:?}", unit);
     // >> Meet the unnit value: ()
 
     // Компилятор предупреждает о неиспользуемых переменных; эти предупреждения можно скрыть, поставив знак подчёркивания в начало имени переменной
@@ -34,16 +40,17 @@ fn binding_variable() {
 // 4.1 Изменяемость
 // По умолчанию связывание переменных является неизменяемым, но с помощью модификатора `mut` изменения можно разрешить.
 
-fn changeability() {
+/// Изменяемая переменная `mut` и её присваивание.
+pub fn changeability(out: &Output) {
     let _immutable_binding = 1;
     let mut mutable_binding = 1;
 
-    println!("Перед изменением: {}", mutable_binding);
+    writeln!(out, "Перед изменением: {}", mutable_binding);
     // >> Перед изменением: 1
 
     mutable_binding += 1;
 
-    println!("После изменения: {}", mutable_binding);
+    writeln!(out, "После изменения: {}", mutable_binding);
     // >> После изменения: 2
 
     // Ошибка! Переменная не изменяемая
@@ -56,42 +63,43 @@ fn changeability() {
 Блок — это набор инструкций, заключённый между фигурными скобками {}.
 */
 
-fn advantage_and_shading_area() {
+/// Блоки, время жизни привязок и затенение переменных.
+pub fn advantage_and_shading_area(out: &Output) {
     // Эта переменная живёт в функции main
     let long_lived_binding = 1;
 
     // Это блок, он имеет меньшую область видимости, чем функция main
     {
         let short_lived_binding = 2;
-        println!("inner short: {}", short_lived_binding);
+        writeln!(out, "inner short: {}", short_lived_binding);
         // >> inner short: 2
         // При этом в блоке доступна переменная из `main`
-        println!("outer long: {}", long_lived_binding);
+        writeln!(out, "outer long: {}", long_lived_binding);
         // >> outer long: 1
     }
     // Ошибка! `short_lived_binding` нет в этой области видимости
-    // println!("outer short: {}", short_living_binding);
-    println!("outer long: {}", long_lived_binding);
+    // writeln!(out, "outer short: {}", short_living_binding);
+    writeln!(out, "outer long: {}", long_lived_binding);
     // >> outer long: 1
 
     let shadowed_binding = 1;
 
     {
-        println!("До затенения: {}", shadowed_binding);
+        writeln!(out, "До затенения: {}", shadowed_binding);
         // >> До затенения: 1
 
         // Эта переменная *затеняет* внешнюю
         let shadowed_binding = "abc";
 
-        println!("Затеннёная во внутреннем блоке: {}", shadowed_binding);
+        writeln!(out, "Затеннёная во внутреннем блоке: {}", shadowed_binding);
         // >> Затеннёная во внутреннем блоке: abc
     }
-    println!("Во внешнем блоке: {}", shadowed_binding);
+    writeln!(out, "Во внешнем блоке: {}", shadowed_binding);
     // >> Во внешнем блоке: 1
 
     // Эта привязка *затеняет* предыдущую
     let shadowed_binding = 2;
-    println!("Затеннёная во внешнем блоке: {}", shadowed_binding);
+    writeln!(out, "Затеннёная во внешнем блоке: {}", shadowed_binding);
     // >> Затеннёная во внешнем блоке: 2
 }
 
@@ -101,7 +109,8 @@ fn advantage_and_shading_area() {
 Однако такая форма используется редко, так как может привести к использованию неинициализированных переменных.
 */
 
-fn pre_announcement() {
+/// Объявление переменной без значения и её инициализация позже.
+pub fn pre_announcement(out: &Output) {
     // Объявляем связь с переменной
     let a_binding;
 
@@ -110,17 +119,18 @@ fn pre_announcement() {
         // Инициализируем связь
         a_binding = x * x;
     }
-    println!("Связь a: {}", a_binding);
+    writeln!(out, "Связь a: {}", a_binding);
     // >> Связь a: 4
 
+    #[allow(clippy::needless_late_init)]
     let another_binding;
 
     // Ошибка! Использование неинициализированной связи с переменной
-    // println!("другая связь: {}", another_binding);
+    // writeln!(out, "другая связь: {}", another_binding);
 
     another_binding = 1;
 
-    println!("другая связь: {}", another_binding);
+    writeln!(out, "другая связь: {}", another_binding);
     // >> другая связь: 1
     /*
     Компилятор запрещает использование неинициализированных переменных,
@@ -133,11 +143,15 @@ fn pre_announcement() {
 Когда данные неизменяемо привязаны к тому же имени, они замораживаются.
 Замороженные данные не могут быть изменены до тех пор, пока неизменяемая привязка не выйдет из области видимости:
 */
-fn freezing() {
+/// Переменная, замороженная неизменяемой привязкой с тем же именем.
+pub fn freezing(out: &Output) {
     let mut _mutable_integer = 7i32;
     {
         // Неизменяемое затенение `_mutable_integer`
         let _mutable_integer = _mutable_integer;
+        // `Traced` отмечает в хронике владения, где заканчивается заморозка:
+        // `_frozen` удаляется вместе с затенением в конце этой области
+        let _frozen = Traced::new("_frozen", _mutable_integer);
 
         // Ошибка! `_mutable_integer` заморожена в этой области
         // _mutable_integer = 50;
@@ -145,13 +159,28 @@ fn freezing() {
         // `_mutable_integer` выходит из области видимости
     }
     _mutable_integer = 3;
-    println!("_mutable_integer: {}", _mutable_integer);
+    writeln!(out, "_mutable_integer: {}", _mutable_integer);
     // >> _mutable_integer: 3
 }
-pub fn run4() {
-    binding_variable();
-    changeability();
-    advantage_and_shading_area();
-    pre_announcement();
-    freezing();
+
+/// Глава целиком: все разделы подряд, без заголовков.
+pub fn run4(out: &Output) {
+    binding_variable(out);
+    changeability(out);
+    advantage_and_shading_area(out);
+    pre_announcement(out);
+    freezing(out);
 }
+
+/// Разделы главы 4 в порядке книги.
+pub const SECTIONS: &[Section] = &[
+    section!("4", "Связывание переменных", binding_variable),
+    section!("4.1", "Изменяемость", changeability),
+    section!(
+        "4.2",
+        "Область видимости и затенение",
+        advantage_and_shading_area
+    ),
+    section!("4.3", "Предварительное объявление", pre_announcement),
+    section!("4.4", "Заморозка", freezing),
+];
@@ -0,0 +1,99 @@
+// `start verify`: сверка фактического вывода разделов с аннотациями `// >>` в исходниках
+
+use crate::annotations::{self, Annotation, Change};
+use crate::book::Section;
+use crate::i18n::{self, Lang};
+use crate::runner;
+use crate::tr;
+
+enum Outcome {
+    Ok,
+    Mismatch,
+    NoAnnotations,
+    Failed,
+}
+
+// Возвращает `true`, если вывод всех разделов совпал с аннотациями
+pub fn verify(sections: &[&Section]) -> bool {
+    let (mut ok, mut mismatched, mut unannotated, mut failed) = (0, 0, 0, 0);
+
+    for section in sections.iter().filter(|section| section.run.is_some()) {
+        match verify_section(section) {
+            Outcome::Ok => ok += 1,
+            Outcome::Mismatch => mismatched += 1,
+            Outcome::NoAnnotations => unannotated += 1,
+            Outcome::Failed => failed += 1,
+        }
+    }
+
+    println!();
+    println!("{}", tr!(VERIFY_TOTAL, ok, mismatched, unannotated, failed));
+    mismatched == 0 && failed == 0
+}
+
+fn verify_section(section: &Section) -> Outcome {
+    let title = format!(
+        "{} {} ({})",
+        section.number,
+        section.title(),
+        section.func()
+    );
+    let expected = annotations::expected(section);
+
+    // Аннотации записаны по-русски, поэтому и вывод для сверки — русский
+    let output = match i18n::with_lang(Lang::Ru, || runner::capture(section)) {
+        Ok(_) if section.should_panic => {
+            println!("{}", tr!(STATUS_ERROR, title));
+            println!("    {}", tr!(VERIFY_SHOULD_PANIC));
+            return Outcome::Failed;
+        }
+        Ok(output) => output,
+        Err(err) if section.should_panic => {
+            println!("{}", tr!(VERIFY_EXPECTED_PANIC, title, err));
+            return Outcome::Ok;
+        }
+        Err(err) => {
+            println!("{}", tr!(STATUS_ERROR, title));
+            println!("    {}", err.replace('\n', "\n    "));
+            return Outcome::Failed;
+        }
+    };
+    if expected.is_empty() {
+        println!("{}", tr!(STATUS_NO_ANNOTATIONS, title));
+        return Outcome::NoAnnotations;
+    }
+
+    let (actual_lines, changes) = compare(&expected, &output);
+    if changes
+        .iter()
+        .all(|change| matches!(change, Change::Same(..)))
+    {
+        println!("{}", tr!(STATUS_OK, title));
+        return Outcome::Ok;
+    }
+
+    println!("{}", tr!(STATUS_MISMATCH, title));
+    for change in changes {
+        match change {
+            Change::Same(..) => {}
+            Change::Missing(i) => println!(
+                "    - {}    ({}:{})",
+                expected[i].text,
+                section.file,
+                expected[i].line + 1
+            ),
+            Change::Unexpected(j) => println!("    + {}", actual_lines[j]),
+        }
+    }
+    Outcome::Mismatch
+}
+
+// Сравнивает вывод с аннотациями. Возвращает строки вывода в том виде,
+// в котором они сравнивались, и разницу с аннотациями
+pub fn compare<'a>(expected: &[Annotation], output: &'a str) -> (Vec<&'a str>, Vec<Change>) {
+    let expected_lines: Vec<&str> = expected.iter().map(|a| a.text.as_str()).collect();
+    // Пробелы в конце строки в аннотации не записать, поэтому они не сравниваются
+    let actual_lines: Vec<&str> = output.lines().map(str::trim_end).collect();
+    let changes = annotations::diff(&expected_lines, &actual_lines);
+    (actual_lines, changes)
+}
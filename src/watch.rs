@@ -0,0 +1,225 @@
+// `start watch 1.2.2`: следит за `src/` и после каждого сохранения пересобирает книгу
+// через `cargo build` и запускает только выбранные разделы: их вывод, сверку с аннотациями
+// и проверку заданий, если они есть.
+//
+// Запущенная программа не может подменить собственный код, поэтому разделы
+// запускает свежесобранный `start` отдельным процессом: тот файл, о котором сообщил
+// `cargo build`, а не текущая программа (её могли запустить из другого каталога сборки).
+// В Windows запущенный файл заблокирован, и `cargo build` не сможет его перезаписать:
+// там запускайте `watch` из копии программы.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::book::{self, Selection};
+use crate::check;
+use crate::i18n;
+use crate::tr;
+
+// Как часто проверять, изменились ли файлы
+const INTERVAL: Duration = Duration::from_millis(500);
+
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+// `args` — номера разделов в том виде, в каком их передали в командной строке.
+// Работает, пока его не прервут (Ctrl+C); возвращает ошибку, если следить не за чем.
+pub fn watch(selection: &Selection, args: &[String]) -> Result<(), String> {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let src = root.join("src");
+    let mut snapshot = scan(&src);
+    if snapshot.is_empty() {
+        return Err(tr!(WATCH_NO_SOURCES, src.display()));
+    }
+    let exercises = !check::select(selection).is_empty();
+
+    build_and_run(root, args, exercises);
+    loop {
+        println!();
+        println!("{}", tr!(WATCH_WAITING, src.display()));
+        let changed = loop {
+            thread::sleep(INTERVAL);
+            let current = scan(&src);
+            if let Some(path) = changed(&snapshot, &current) {
+                snapshot = current;
+                break path;
+            }
+        };
+
+        println!();
+        let changed = changed.strip_prefix(root).unwrap_or(&changed);
+        println!("{}", tr!(WATCH_CHANGED, changed.display()));
+        build_and_run(root, args, exercises);
+    }
+}
+
+fn build_and_run(root: &Path, args: &[String], exercises: bool) {
+    match build(root) {
+        Some(exe) => run(&exe, args, exercises),
+        None => println!("{}", tr!(WATCH_BUILD_FAILED)),
+    }
+}
+
+// Время изменения каждого файла в каталоге и подкаталогах
+fn scan(dir: &Path) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+    let mut dirs = vec![dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                dirs.push(path);
+            } else if let Ok(modified) = metadata.modified() {
+                snapshot.insert(path, modified);
+            }
+        }
+    }
+    snapshot
+}
+
+// Первый изменённый, добавленный или удалённый файл
+fn changed(before: &Snapshot, after: &Snapshot) -> Option<PathBuf> {
+    after
+        .iter()
+        .find(|(path, modified)| before.get(*path) != Some(modified))
+        .map(|(path, _)| path)
+        .or_else(|| before.keys().find(|path| !after.contains_key(*path)))
+        .cloned()
+}
+
+// Сборка тем же профилем и с теми же главами, что и текущая программа.
+// Возвращает путь к собранной программе; ошибки `cargo` видны в терминале.
+fn build(root: &Path) -> Option<PathBuf> {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let mut command = Command::new(cargo);
+    command.current_dir(root).args([
+        "build",
+        "--quiet",
+        "--bin",
+        env!("CARGO_PKG_NAME"),
+        "--message-format=json-render-diagnostics",
+        "--no-default-features",
+    ]);
+    let features = book::features();
+    if !features.is_empty() {
+        command.args(["--features", &features.join(",")]);
+    }
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+    // Сообщения в JSON приходят в stdout, а ошибки компиляции — как обычно, в stderr
+    let output = match command.stderr(Stdio::inherit()).output() {
+        Ok(output) => output,
+        Err(err) => {
+            println!("{}", tr!(WATCH_NO_CARGO, err));
+            return None;
+        }
+    };
+    if !output.status.success() {
+        return None;
+    }
+    let exe = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(executable);
+    if exe.is_none() {
+        println!("{}", tr!(WATCH_NO_EXECUTABLE));
+    }
+    exe.map(PathBuf::from)
+}
+
+// Путь к программе из сообщения `cargo build --message-format=json`:
+// `{"reason":"compiler-artifact",...,"executable":"/.../target/debug/start",...}`.
+// У библиотеки `executable` равен `null`.
+fn executable(message: &str) -> Option<String> {
+    let (_, rest) = message.split_once("\"executable\":\"")?;
+    let mut path = String::new();
+    let mut chars = rest.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return Some(path),
+            '\\' => path.push(chars.next()?),
+            c => path.push(c),
+        }
+    }
+    None
+}
+
+// Вывод разделов, сверка с аннотациями и проверка заданий в свежесобранной программе,
+// на том же языке, что и у `watch`
+fn run(exe: &Path, args: &[String], exercises: bool) {
+    let mut commands: Vec<Vec<&str>> = vec![vec![], vec!["verify"]];
+    if exercises {
+        commands.push(vec!["check"]);
+    }
+    for command in commands {
+        println!();
+        let status = Command::new(exe)
+            .args(&command)
+            .args(args)
+            .args(["--lang", i18n::lang().code()])
+            .status();
+        if let Err(err) = status {
+            println!("{}", tr!(WATCH_RUN_FAILED, exe.display(), err));
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_executable_in_cargo_messages() {
+        let bin = r#"{"reason":"compiler-artifact","target":{"name":"start"},"executable":"/книга/target/debug/start","fresh":true}"#;
+        assert_eq!(
+            executable(bin).as_deref(),
+            Some("/книга/target/debug/start")
+        );
+        let windows = r#"{"executable":"C:\\book\\target\\debug\\start.exe"}"#;
+        assert_eq!(
+            executable(windows).as_deref(),
+            Some(r"C:\book\target\debug\start.exe")
+        );
+        let lib = r#"{"reason":"compiler-artifact","target":{"name":"start"},"executable":null}"#;
+        assert_eq!(executable(lib), None);
+        assert_eq!(
+            executable(r#"{"reason":"build-finished","success":true}"#),
+            None
+        );
+    }
+
+    #[test]
+    fn notices_changed_added_and_removed_files() {
+        let time = |secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+        let before: Snapshot = [
+            (PathBuf::from("a.rs"), time(1)),
+            (PathBuf::from("b.rs"), time(1)),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(changed(&before, &before.clone()), None);
+
+        let mut after = before.clone();
+        after.insert(PathBuf::from("b.rs"), time(2));
+        assert_eq!(changed(&before, &after), Some(PathBuf::from("b.rs")));
+
+        let mut after = before.clone();
+        after.insert(PathBuf::from("c.rs"), time(1));
+        assert_eq!(changed(&before, &after), Some(PathBuf::from("c.rs")));
+
+        let mut after = before.clone();
+        after.remove(Path::new("a.rs"));
+        assert_eq!(changed(&before, &after), Some(PathBuf::from("a.rs")));
+    }
+}